@@ -1,5 +1,14 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use direct_ring_buffer::create_ring_buffer;
+use std::{
+    hint::spin_loop,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 
 fn bench_elements<T: Copy + Default>(c: &mut Criterion) {
@@ -26,5 +35,206 @@ fn bench_elements<T: Copy + Default>(c: &mut Criterion) {
     });
 }
 
+fn bench_batch<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    const BUFFER_SIZE: usize = 4096;
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+
+    c.bench_function(&format!("write_element/advance({type_name}) ({BUFFER_SIZE} elements)"), |b| {
+        b.iter(|| {
+            for _ in 0..BUFFER_SIZE {
+                black_box(producer.write_element(black_box(T::default())));
+            }
+            black_box(consumer.advance(BUFFER_SIZE));
+        });
+    });
+
+    c.bench_function(&format!("batch write_element/advance({type_name}) ({BUFFER_SIZE} elements)"), |b| {
+        b.iter(|| {
+            let mut batch = producer.batch();
+            for _ in 0..BUFFER_SIZE {
+                black_box(batch.write_element(black_box(T::default())));
+            }
+            drop(batch);
+            black_box(consumer.advance(BUFFER_SIZE));
+        });
+    });
+}
+
+fn bench_slices<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    const BUFFER_SIZE: usize = 4096;
+    const CHUNK_SIZE: usize = 1000;
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    let src = vec![T::default(); CHUNK_SIZE];
+    let mut dst = vec![T::default(); CHUNK_SIZE];
+
+    c.bench_function(&format!("write_from_slice/read_into_slice({type_name}) ({CHUNK_SIZE} elements)"), |b| {
+        b.iter(|| {
+            black_box(producer.write_from_slice(black_box(&src)));
+            black_box(consumer.read_into_slice(black_box(&mut dst)));
+        });
+    });
+
+    // The same copies through the closure-based methods, for comparison
+    c.bench_function(&format!("write_slices/read_slices({type_name}) ({CHUNK_SIZE} elements)"), |b| {
+        b.iter(|| {
+            black_box(producer.write_slices(|data, offset| {
+                data.copy_from_slice(&src[offset..offset + data.len()]);
+                data.len()
+            }, Some(CHUNK_SIZE)));
+            black_box(consumer.read_slices(|data, offset| {
+                dst[offset..offset + data.len()].copy_from_slice(data);
+                data.len()
+            }, Some(CHUNK_SIZE)));
+        });
+    });
+}
+
+fn bench_chunks<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    const CHUNK_SIZES: [usize; 4] = [16, 256, 4096, 65536];
+    // Not a multiple of the chunk sizes, so that the chunks wrap around
+    const BUFFER_SIZE: usize = 100_000;
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    let mut group = c.benchmark_group(format!("write_slices/read_slices({type_name})"));
+
+    for chunk_size in CHUNK_SIZES {
+        let src = vec![T::default(); chunk_size];
+        let mut dst = vec![T::default(); chunk_size];
+        group.throughput(Throughput::Elements(chunk_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(chunk_size), &chunk_size, |b, &chunk_size| {
+            b.iter(|| {
+                black_box(producer.write_slices(|data, offset| {
+                    data.copy_from_slice(&src[offset..offset + data.len()]);
+                    data.len()
+                }, Some(chunk_size)));
+                black_box(consumer.read_slices(|data, offset| {
+                    dst[offset..offset + data.len()].copy_from_slice(data);
+                    data.len()
+                }, Some(chunk_size)));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_fill<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    const BUFFER_SIZE: usize = 4096;
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+
+    c.bench_function(&format!("fill_with/advance({type_name}) ({BUFFER_SIZE} elements)"), |b| {
+        b.iter(|| {
+            black_box(producer.fill_with(black_box(T::default())));
+            black_box(consumer.advance(BUFFER_SIZE));
+        });
+    });
+
+    // Filling a plain slice of the same size, as the baseline for the fill loop
+    let mut slice = vec![T::default(); BUFFER_SIZE];
+    c.bench_function(&format!("slice::fill({type_name}) ({BUFFER_SIZE} elements)"), |b| {
+        b.iter(|| {
+            black_box(&mut slice).fill(black_box(T::default()));
+        });
+    });
+}
+
+fn bench_ping_pong(c: &mut Criterion) {
+    const STOP: u64 = u64::MAX;
+    let (mut request_producer, mut request_consumer) = create_ring_buffer::<u64>(64);
+    let (mut response_producer, mut response_consumer) = create_ring_buffer::<u64>(64);
+
+    // Echoes every element back until the stop value is received
+    let echo = thread::spawn(move || loop {
+        let Some(value) = request_consumer.read_element() else {
+            spin_loop();
+            continue;
+        };
+        while !response_producer.write_element(value) {
+            spin_loop();
+        }
+        if value == STOP {
+            break;
+        }
+    });
+
+    // Each iteration is a round trip between two threads, so the time is
+    // twice the one-way latency. Both threads spin, so the results are only
+    // meaningful with at least two cores available
+    c.bench_function("ping-pong round trip (u64)", |b| {
+        b.iter_custom(|iters| {
+            let start = Instant::now();
+            for i in 0..iters {
+                while !request_producer.write_element(i) {
+                    spin_loop();
+                }
+                loop {
+                    if let Some(value) = response_consumer.read_element() {
+                        debug_assert_eq!(value, i);
+                        break;
+                    }
+                    spin_loop();
+                }
+            }
+            start.elapsed()
+        });
+    });
+
+    request_producer.write_element(STOP);
+    echo.join().unwrap();
+}
+
+fn bench_cross_thread<T: Copy + Default + Send + 'static>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    const BUFFER_SIZE: usize = 65536;
+    const CHUNK_SIZE: usize = 4096;
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // Keeps the buffer as full as possible while the consumer is measured
+    let writer = thread::spawn({
+        let stop = Arc::clone(&stop);
+        move || {
+            let src = vec![T::default(); CHUNK_SIZE];
+            while !stop.load(Ordering::Relaxed) {
+                if producer.write_from_slice(&src) == 0 {
+                    spin_loop();
+                }
+            }
+        }
+    });
+
+    let mut group = c.benchmark_group(format!("cross-thread write_from_slice/read_into_slice({type_name})"));
+    group.throughput(Throughput::Elements(CHUNK_SIZE as u64));
+    group.bench_function(BenchmarkId::from_parameter(CHUNK_SIZE), |b| {
+        let mut dst = [T::default(); CHUNK_SIZE];
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let start = Instant::now();
+                let mut read = 0;
+                while read < CHUNK_SIZE {
+                    match consumer.read_into_slice(&mut dst[read..]) {
+                        0 => spin_loop(),
+                        n => read += n,
+                    }
+                }
+                elapsed += start.elapsed();
+            }
+            elapsed
+        });
+    });
+    group.finish();
+
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+}
+
 criterion_group!(bench_group_elements, bench_elements::<u8>, bench_elements::<u16>, bench_elements::<u32>, bench_elements::<usize>);
-criterion_main!(bench_group_elements);
+criterion_group!(bench_group_batch, bench_batch::<u8>, bench_batch::<f32>);
+criterion_group!(bench_group_slices, bench_slices::<u8>, bench_slices::<f32>);
+criterion_group!(bench_group_fill, bench_fill::<u8>, bench_fill::<f32>);
+criterion_group!(bench_group_chunks, bench_chunks::<u8>, bench_chunks::<f32>, bench_chunks::<u64>);
+criterion_group!(bench_group_threads, bench_ping_pong, bench_cross_thread::<u8>, bench_cross_thread::<f32>);
+criterion_main!(bench_group_elements, bench_group_batch, bench_group_slices, bench_group_fill, bench_group_chunks, bench_group_threads);
@@ -0,0 +1,107 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use direct_ring_buffer::create_ring_buffer;
+use ringbuf::{
+    traits::{Consumer as _, Producer as _, Split as _},
+    HeapRb,
+};
+
+// Not a multiple of the chunk size, so that the chunks wrap around
+const BUFFER_SIZE: usize = 4099;
+const CHUNK_SIZE: usize = 1000;
+
+fn bench_elements<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    let mut group = c.benchmark_group(format!("elements({type_name})"));
+    group.throughput(Throughput::Elements(CHUNK_SIZE as u64));
+
+    // Each iteration writes a chunk of elements one by one, then reads them
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    group.bench_function("direct_ring_buffer", |b| {
+        b.iter(|| {
+            for _ in 0..CHUNK_SIZE {
+                black_box(producer.write_element(black_box(T::default())));
+            }
+            for _ in 0..CHUNK_SIZE {
+                black_box(consumer.read_element());
+            }
+        });
+    });
+
+    let (mut producer, mut consumer) = HeapRb::<T>::new(BUFFER_SIZE).split();
+    group.bench_function("ringbuf", |b| {
+        b.iter(|| {
+            for _ in 0..CHUNK_SIZE {
+                black_box(producer.try_push(black_box(T::default())).is_ok());
+            }
+            for _ in 0..CHUNK_SIZE {
+                black_box(consumer.try_pop());
+            }
+        });
+    });
+
+    let (mut producer, mut consumer) = rtrb::RingBuffer::<T>::new(BUFFER_SIZE);
+    group.bench_function("rtrb", |b| {
+        b.iter(|| {
+            for _ in 0..CHUNK_SIZE {
+                black_box(producer.push(black_box(T::default())).is_ok());
+            }
+            for _ in 0..CHUNK_SIZE {
+                black_box(consumer.pop().ok());
+            }
+        });
+    });
+    group.finish();
+}
+
+fn bench_slices<T: Copy + Default>(c: &mut Criterion) {
+    let type_name = std::any::type_name::<T>();
+    let mut group = c.benchmark_group(format!("slices({type_name})"));
+    group.throughput(Throughput::Elements(CHUNK_SIZE as u64));
+    let src = vec![T::default(); CHUNK_SIZE];
+    let mut dst = vec![T::default(); CHUNK_SIZE];
+
+    // Each iteration copies a chunk from a slice into the buffer, then out to
+    // another slice
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    group.bench_function("direct_ring_buffer", |b| {
+        b.iter(|| {
+            black_box(producer.write_from_slice(black_box(&src)));
+            black_box(consumer.read_into_slice(black_box(&mut dst)));
+        });
+    });
+
+    let (mut producer, mut consumer) = create_ring_buffer::<T>(BUFFER_SIZE);
+    group.bench_function("direct_ring_buffer (write_slices/read_slices)", |b| {
+        b.iter(|| {
+            black_box(producer.write_slices(|data, offset| {
+                data.copy_from_slice(&src[offset..offset + data.len()]);
+                data.len()
+            }, Some(CHUNK_SIZE)));
+            black_box(consumer.read_slices(|data, offset| {
+                dst[offset..offset + data.len()].copy_from_slice(data);
+                data.len()
+            }, Some(CHUNK_SIZE)));
+        });
+    });
+
+    let (mut producer, mut consumer) = HeapRb::<T>::new(BUFFER_SIZE).split();
+    group.bench_function("ringbuf", |b| {
+        b.iter(|| {
+            black_box(producer.push_slice(black_box(&src)));
+            black_box(consumer.pop_slice(black_box(&mut dst)));
+        });
+    });
+
+    let (mut producer, mut consumer) = rtrb::RingBuffer::<T>::new(BUFFER_SIZE);
+    group.bench_function("rtrb", |b| {
+        b.iter(|| {
+            black_box(producer.push_partial_slice(black_box(&src)));
+            black_box(consumer.pop_partial_slice(black_box(&mut dst)));
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(bench_group_elements, bench_elements::<u8>, bench_elements::<f32>);
+criterion_group!(bench_group_slices, bench_slices::<u8>, bench_slices::<f32>);
+criterion_main!(bench_group_elements, bench_group_slices);
@@ -0,0 +1,80 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::io;
+
+use futures_io::{AsyncBufRead, AsyncRead};
+
+use crate::{AsyncConsumer, Index};
+
+/// Reads the bytes written to the ring buffer, waiting for them if it is empty.
+///
+/// A read returns 0 bytes, the end of the stream, once the ring buffer is empty
+/// and the producer has been dropped.
+///
+/// This implementation is available only with the `futures-io` feature.
+impl<I: Index> AsyncRead for AsyncConsumer<u8, I> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        match this.poll_readable(cx) {
+            Poll::Ready(true) => Poll::Ready(Ok(this.get_mut().read_into_slice(buf))),
+            Poll::Ready(false) => Poll::Ready(Ok(0)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Exposes the readable bytes of the ring buffer without copying them.
+///
+/// [`AsyncBufRead::poll_fill_buf`] returns the first contiguous readable slice
+/// of the storage, waiting for bytes if the ring buffer is empty, and
+/// [`AsyncBufRead::consume`] frees the bytes for the producer. A slice that
+/// ends at the wrap-around point is followed by the rest of the bytes once it
+/// has been consumed.
+///
+/// This implementation is available only with the `futures-io` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use futures::{executor::block_on, AsyncBufReadExt};
+///
+/// let (mut producer, consumer) = create_ring_buffer::<u8>(16);
+/// producer.write_exact(b"hello\nworld\n");
+/// drop(producer);
+/// let mut lines = Vec::new();
+/// let mut consumer = consumer.into_async();
+/// block_on(async {
+///     let mut line = String::new();
+///     while consumer.read_line(&mut line).await.unwrap() != 0 {
+///         lines.push(line.clone());
+///         line.clear();
+///     }
+/// });
+/// assert_eq!(lines, ["hello\n", "world\n"]);
+/// ```
+impl<I: Index> AsyncBufRead for AsyncConsumer<u8, I> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        match this.poll_readable(cx) {
+            Poll::Ready(true) => Poll::Ready(Ok(this.get_ref().as_slices().0)),
+            Poll::Ready(false) => Poll::Ready(Ok(&[])),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let consumer = self.get_mut().get_mut();
+        debug_assert!(amt <= consumer.available(), "consumed more bytes than read");
+        consumer.advance(amt);
+    }
+}
@@ -0,0 +1,566 @@
+use core::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    sync::{Arc, Ordering},
+    Consumer, DirectRingBuffer, Index, Producer,
+};
+
+/// Producer part of the ring buffer for asynchronous tasks.
+///
+/// This struct wraps a [`Producer`], created with [`Producer::into_async`],
+/// and adds methods that wait for space without blocking the thread. The task
+/// waiting for space is woken when the consumer frees space or is dropped. The
+/// waker is registered in the ring buffer itself, so no executor or timer is
+/// involved, and the methods work with any executor. Only [`core::task`] is
+/// used, so the async layer is available without `std` as well.
+///
+/// The other methods of the producer are available through
+/// [`AsyncProducer::get_mut`].
+///
+/// # Cancellation
+///
+/// Dropping a future returned by a method of this struct before it completes
+/// never loses or duplicates elements. [`AsyncProducer::write_slices`] waits
+/// without writing anything, and writes within the poll that completes it, so
+/// a dropped future has written nothing. The elements that the closure reports
+/// as written are published, and the rest of the space is left untouched.
+/// [`WriteAll`] publishes the elements it writes in each poll, so a dropped
+/// future leaves the first [`WriteAll::written`] elements of the slice in the
+/// ring buffer, and writing the rest of the slice resumes where it stopped.
+///
+/// This struct is available only with the `async` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use futures::executor::block_on;
+///
+/// let (producer, consumer) = create_ring_buffer::<u8>(4);
+/// let (mut producer, mut consumer) = (producer.into_async(), consumer.into_async());
+/// block_on(async {
+///     let written = producer
+///         .write_slices(|data, _| {
+///             data.fill(1);
+///             data.len()
+///         }, None)
+///         .await;
+///     assert_eq!(written, 4);
+///     let read = consumer.read_slices(|data, _| data.len(), None).await;
+///     assert_eq!(read, 4);
+/// });
+/// ```
+pub struct AsyncProducer<T, I: Index = usize> {
+    producer: Producer<T, I>,
+}
+
+impl<T, I: Index> AsyncProducer<T, I> {
+    /// Returns a reference to the wrapped producer.
+    pub fn get_ref(&self) -> &Producer<T, I> {
+        &self.producer
+    }
+
+    /// Returns a mutable reference to the wrapped producer.
+    pub fn get_mut(&mut self) -> &mut Producer<T, I> {
+        &mut self.producer
+    }
+
+    /// Unwraps the producer.
+    pub fn into_inner(self) -> Producer<T, I> {
+        self.producer
+    }
+
+    /// Waits until the consumer is dropped.
+    ///
+    /// See [`Producer::closed`] for details.
+    pub fn closed(&self) -> PeerDropped<T, I> {
+        self.producer.closed()
+    }
+
+    /// Polls for space in the ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// `Poll::Ready(true)` if at least one element can be written,
+    /// `Poll::Ready(false)` if the consumer has been dropped, or
+    /// `Poll::Pending` after registering the task to be woken when the consumer
+    /// frees space or is dropped.
+    pub fn poll_writable(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        let buffer = &self.producer.buffer;
+        let ready = || {
+            if buffer.is_consumer_dropped() {
+                Some(false)
+            } else if buffer.available_write() != 0 {
+                Some(true)
+            } else {
+                None
+            }
+        };
+        if let Some(ready) = ready() {
+            return Poll::Ready(ready);
+        }
+        buffer.space_waker.register(cx.waker());
+        match ready() {
+            Some(ready) => Poll::Ready(ready),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Writes elements to the ring buffer, waiting for space if it is full.
+    ///
+    /// Once space is available, this method behaves like
+    /// [`Producer::write_slices`]. The writing takes place within a single poll,
+    /// so dropping the future before it completes writes nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function to write the elements, as with
+    ///   [`Producer::write_slices`].
+    /// * `max_size` - The maximum number of elements to write. If `None`, all
+    ///   the available space is offered.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written, which is 0 if the consumer has been
+    /// dropped.
+    pub async fn write_slices(
+        &mut self,
+        f: impl FnMut(&mut [T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        if !poll_fn(|cx| self.poll_writable(cx)).await {
+            return 0;
+        }
+        self.producer.write_slices(f, max_size)
+    }
+
+    /// Writes all elements of a slice, waiting for space as needed.
+    ///
+    /// The returned future writes as many elements as fit each time it is
+    /// polled, and completes once all of them are written or the consumer has
+    /// been dropped. Dropping the future before it completes leaves the
+    /// elements written so far in the ring buffer, and
+    /// [`WriteAll::written`] tells how many they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The elements to write.
+    ///
+    /// # Returns
+    ///
+    /// A future resolving to the number of elements written, which is less
+    /// than the length of `src` only if the consumer has been dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::executor::block_on;
+    ///
+    /// let (producer, consumer) = create_ring_buffer::<u8>(4);
+    /// let (mut producer, mut consumer) = (producer.into_async(), consumer.into_async());
+    /// let writer = std::thread::spawn(move || block_on(producer.write_all(&[1; 100])));
+    /// let mut dst = [0; 100];
+    /// assert_eq!(block_on(consumer.read_exact(&mut dst)), 100);
+    /// assert_eq!(writer.join().unwrap(), 100);
+    /// ```
+    pub fn write_all<'a>(&'a mut self, src: &'a [T]) -> WriteAll<'a, T, I>
+    where
+        T: Copy,
+    {
+        WriteAll {
+            producer: self,
+            src,
+            written: 0,
+        }
+    }
+}
+
+/// Future returned by [`AsyncProducer::write_all`].
+#[must_use = "futures do nothing unless polled"]
+pub struct WriteAll<'a, T, I: Index = usize> {
+    producer: &'a mut AsyncProducer<T, I>,
+    src: &'a [T],
+    written: usize,
+}
+
+impl<T, I: Index> WriteAll<'_, T, I> {
+    /// Returns the number of elements written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
+
+impl<T: Copy, I: Index> Future for WriteAll<'_, T, I> {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = self.get_mut();
+        while this.written != this.src.len() {
+            match this.producer.poll_writable(cx) {
+                Poll::Ready(true) => {
+                    let src = &this.src[this.written..];
+                    this.written += this.producer.producer.write_from_slice(src);
+                }
+                Poll::Ready(false) => break,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(this.written)
+    }
+}
+
+impl<T, I: Index> fmt::Debug for WriteAll<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteAll")
+            .field("len", &self.src.len())
+            .field("written", &self.written)
+            .finish()
+    }
+}
+
+impl<T, I: Index> Producer<T, I> {
+    /// Converts the producer into one that waits for space asynchronously.
+    ///
+    /// See [`AsyncProducer`] for details.
+    ///
+    /// This method is available only with the `async` feature.
+    pub fn into_async(self) -> AsyncProducer<T, I> {
+        AsyncProducer { producer: self }
+    }
+
+    /// Waits until the consumer is dropped.
+    ///
+    /// The returned future completes once the consumer has been dropped, so
+    /// that a task can shut down as soon as nothing reads the elements anymore,
+    /// for example by racing it against its work with `select!`. It does not
+    /// borrow the producer, and does not interfere with the methods waiting for
+    /// space, but only one task can wait for the consumer to be dropped at a
+    /// time: if several tasks do, only the one that polled last is woken. The
+    /// future keeps the ring buffer alive, so [`Producer::reunite`] fails while
+    /// it exists.
+    ///
+    /// This method is available only with the `async` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::executor::block_on;
+    ///
+    /// let (producer, consumer) = create_ring_buffer::<u8>(4);
+    /// let reader = std::thread::spawn(move || drop(consumer));
+    /// block_on(producer.closed());
+    /// reader.join().unwrap();
+    /// ```
+    pub fn closed(&self) -> PeerDropped<T, I> {
+        PeerDropped {
+            buffer: Arc::clone(&self.buffer),
+            producer: false,
+        }
+    }
+}
+
+impl<T, I: Index> fmt::Debug for AsyncProducer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsyncProducer")
+            .field(&self.producer)
+            .finish()
+    }
+}
+
+/// Consumer part of the ring buffer for asynchronous tasks.
+///
+/// This struct wraps a [`Consumer`], created with [`Consumer::into_async`],
+/// and adds methods that wait for elements without blocking the thread. The
+/// task waiting for elements is woken when the producer writes elements or is
+/// dropped.
+///
+/// The other methods of the consumer are available through
+/// [`AsyncConsumer::get_mut`].
+///
+/// # Cancellation
+///
+/// As with [`AsyncProducer`], dropping a future before it completes never
+/// loses or duplicates elements. [`AsyncConsumer::read_slices`] reads within
+/// the poll that completes it, and frees only the elements the closure reports
+/// as read. [`ReadExact`] frees the elements it copies in each poll, so a
+/// dropped future leaves the first [`ReadExact::read`] elements in the slice,
+/// and the next ones are still in the ring buffer. The streams of the
+/// `futures` feature read an item within the poll that yields it, and the
+/// bytes returned by `poll_fill_buf` of the `futures-io` feature are freed only
+/// when they are consumed.
+///
+/// # Embedded targets
+///
+/// The async layer works without `std`, for example with an
+/// [embassy](https://embassy.dev) task as the consumer and an interrupt handler
+/// as the producer. The producer wakes the task without locks and without
+/// allocating, so that it can write from an interrupt handler that preempts the
+/// task, even while the task is registering its waker. Polling does not
+/// allocate either: the waker is cloned only when it changes, which does not
+/// allocate with the wakers of embassy. On targets without atomic
+/// read-modify-write operations, the `portable-atomic` feature is needed as
+/// well.
+///
+/// This struct is available only with the `async` feature.
+pub struct AsyncConsumer<T, I: Index = usize> {
+    consumer: Consumer<T, I>,
+}
+
+impl<T, I: Index> AsyncConsumer<T, I> {
+    /// Returns a reference to the wrapped consumer.
+    pub fn get_ref(&self) -> &Consumer<T, I> {
+        &self.consumer
+    }
+
+    /// Returns a mutable reference to the wrapped consumer.
+    pub fn get_mut(&mut self) -> &mut Consumer<T, I> {
+        &mut self.consumer
+    }
+
+    /// Unwraps the consumer.
+    pub fn into_inner(self) -> Consumer<T, I> {
+        self.consumer
+    }
+
+    /// Waits until the producer is dropped.
+    ///
+    /// See [`Consumer::closed`] for details.
+    pub fn closed(&self) -> PeerDropped<T, I> {
+        self.consumer.closed()
+    }
+
+    /// Polls for elements in the ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// `Poll::Ready(true)` if at least one element can be read,
+    /// `Poll::Ready(false)` if the ring buffer is empty and the producer has
+    /// been dropped, or `Poll::Pending` after registering the task to be woken
+    /// when the producer writes elements or is dropped.
+    pub fn poll_readable(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        self.poll_available(cx, 1)
+    }
+
+    /// Polls for at least `n` elements in the ring buffer.
+    ///
+    /// Returns `Poll::Ready(false)` if fewer elements are left and the producer
+    /// has been dropped.
+    pub(crate) fn poll_available(&mut self, cx: &mut Context<'_>, n: usize) -> Poll<bool> {
+        let buffer = &self.consumer.buffer;
+        let ready = || {
+            // The flag is loaded first, so that elements written before the
+            // producer was dropped are seen.
+            let dropped = buffer.is_producer_dropped();
+            if buffer.available_read() >= n {
+                Some(true)
+            } else if dropped {
+                Some(false)
+            } else {
+                None
+            }
+        };
+        if let Some(ready) = ready() {
+            return Poll::Ready(ready);
+        }
+        buffer.data_waker.register(cx.waker());
+        match ready() {
+            Some(ready) => Poll::Ready(ready),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Reads elements from the ring buffer, waiting for elements if it is
+    /// empty.
+    ///
+    /// Once elements are available, this method behaves like
+    /// [`Consumer::read_slices`]. The reading takes place within a single poll,
+    /// so dropping the future before it completes reads nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function to read the elements, as with
+    ///   [`Consumer::read_slices`].
+    /// * `max_size` - The maximum number of elements to read. If `None`, all
+    ///   the available elements are offered.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read, which is 0 if the ring buffer is empty and
+    /// the producer has been dropped.
+    pub async fn read_slices(
+        &mut self,
+        f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize {
+        if !poll_fn(|cx| self.poll_readable(cx)).await {
+            return 0;
+        }
+        self.consumer.read_slices(f, max_size)
+    }
+
+    /// Fills a slice with elements, waiting for them as needed.
+    ///
+    /// The returned future reads as many elements as are available each time
+    /// it is polled, and completes once the slice is filled or the ring buffer
+    /// is empty and the producer has been dropped. Dropping the future before
+    /// it completes leaves the elements read so far in the slice, and
+    /// [`ReadExact::read`] tells how many they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to fill.
+    ///
+    /// # Returns
+    ///
+    /// A future resolving to the number of elements read, which is less than
+    /// the length of `dst` only if the producer has been dropped.
+    pub fn read_exact<'a>(&'a mut self, dst: &'a mut [T]) -> ReadExact<'a, T, I>
+    where
+        T: Copy,
+    {
+        ReadExact {
+            consumer: self,
+            dst,
+            read: 0,
+        }
+    }
+}
+
+/// Future returned by [`AsyncConsumer::read_exact`].
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadExact<'a, T, I: Index = usize> {
+    consumer: &'a mut AsyncConsumer<T, I>,
+    dst: &'a mut [T],
+    read: usize,
+}
+
+impl<T, I: Index> ReadExact<'_, T, I> {
+    /// Returns the number of elements read so far.
+    pub fn read(&self) -> usize {
+        self.read
+    }
+}
+
+impl<T: Copy, I: Index> Future for ReadExact<'_, T, I> {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = self.get_mut();
+        while this.read != this.dst.len() {
+            match this.consumer.poll_readable(cx) {
+                Poll::Ready(true) => {
+                    let dst = &mut this.dst[this.read..];
+                    this.read += this.consumer.consumer.read_into_slice(dst);
+                }
+                Poll::Ready(false) => break,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(this.read)
+    }
+}
+
+impl<T, I: Index> fmt::Debug for ReadExact<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadExact")
+            .field("len", &self.dst.len())
+            .field("read", &self.read)
+            .finish()
+    }
+}
+
+impl<T, I: Index> Consumer<T, I> {
+    /// Converts the consumer into one that waits for elements asynchronously.
+    ///
+    /// See [`AsyncConsumer`] for details.
+    ///
+    /// This method is available only with the `async` feature.
+    pub fn into_async(self) -> AsyncConsumer<T, I> {
+        AsyncConsumer { consumer: self }
+    }
+
+    /// Waits until the producer is dropped.
+    ///
+    /// The returned future completes once the producer has been dropped, even
+    /// if elements are left to read. Like [`Producer::closed`], only one task
+    /// can wait for the producer to be dropped at a time.
+    ///
+    /// This method is available only with the `async` feature.
+    pub fn closed(&self) -> PeerDropped<T, I> {
+        PeerDropped {
+            buffer: Arc::clone(&self.buffer),
+            producer: true,
+        }
+    }
+}
+
+impl<T, I: Index> fmt::Debug for AsyncConsumer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsyncConsumer")
+            .field(&self.consumer)
+            .finish()
+    }
+}
+
+/// Future returned by [`Producer::closed`] and [`Consumer::closed`].
+///
+/// It completes once the other handle of the ring buffer has been dropped. The
+/// future can be sent to another thread only if the elements can be sent,
+/// since it may drop them there as the last owner of the ring buffer.
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (producer, _consumer) = create_ring_buffer::<std::rc::Rc<u8>>(1);
+/// let closed = producer.closed();
+/// std::thread::spawn(move || drop(closed));
+/// ```
+#[must_use = "futures do nothing unless polled"]
+pub struct PeerDropped<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
+    /// `true` if the future waits for the producer, `false` for the consumer.
+    producer: bool,
+}
+
+impl<T, I: Index> Future for PeerDropped<T, I> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let buffer = &self.buffer;
+        let (dropped, slot) = if self.producer {
+            (&buffer.producer_dropped, &buffer.producer_drop_waker)
+        } else {
+            (&buffer.consumer_dropped, &buffer.consumer_drop_waker)
+        };
+        if !dropped.load(Ordering::Acquire) {
+            slot.register(cx.waker());
+            if !dropped.load(Ordering::Acquire) {
+                return Poll::Pending;
+            }
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<T, I: Index> fmt::Debug for PeerDropped<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerDropped")
+            .field("producer", &self.producer)
+            .finish()
+    }
+}
+
+// The future only accesses the elements if it is the last owner of the ring
+// buffer, and then drops the unread ones, as an `Arc` of them would.
+unsafe impl<T: Send, I: Index> Send for PeerDropped<T, I> {}
+unsafe impl<T: Send + Sync, I: Index> Sync for PeerDropped<T, I> {}
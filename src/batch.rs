@@ -0,0 +1,127 @@
+use core::fmt;
+
+use crate::{Index, Producer};
+
+/// Batch of elements written to the ring buffer and published together.
+///
+/// This struct is created by [`Producer::batch`]. The elements written with
+/// [`WriteBatch::write_element`] become readable only after calling
+/// [`WriteBatch::flush`] or dropping the batch, so that the write position
+/// shared with the consumer is updated once per batch instead of once per
+/// element. If the batch is leaked, for example with `mem::forget`, the
+/// elements written since the last flush are never published.
+pub struct WriteBatch<'a, T, I: Index = usize> {
+    producer: &'a mut Producer<T, I>,
+    /// Index of the next element to be written, which is handed back to the
+    /// producer when the batch is flushed.
+    index: usize,
+    /// Number of elements written but not published yet.
+    pending: usize,
+    /// Number of elements known to be writable, including the pending ones.
+    available: usize,
+}
+
+impl<'a, T, I: Index> WriteBatch<'a, T, I> {
+    /// Creates an empty batch at the current write position.
+    pub(crate) fn new(producer: &'a mut Producer<T, I>) -> Self {
+        Self {
+            index: producer.index,
+            producer,
+            pending: 0,
+            available: 0,
+        }
+    }
+
+    /// Writes a single element to the batch.
+    ///
+    /// The element is not readable until the batch is flushed. The shared
+    /// positions are accessed only when the space known to be writable has
+    /// been used up.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The element to write.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the element was written, `false` if the buffer is full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// let mut batch = producer.batch();
+    /// assert!(batch.write_element(1));
+    /// assert!(batch.write_element(2));
+    /// assert!(!batch.write_element(3));
+    /// assert_eq!(consumer.available(), 0);
+    /// drop(batch);
+    /// assert_eq!(consumer.read_array::<2>(), Some([1, 2]));
+    /// ```
+    pub fn write_element(&mut self, value: T) -> bool {
+        if self.pending == self.available {
+            self.available = self.producer.available_cached(self.pending + 1);
+            if self.pending == self.available {
+                return false;
+            }
+        }
+        let buffer = &self.producer.buffer;
+        buffer.write_element(&mut self.index, || value);
+        self.pending += 1;
+        true
+    }
+
+    /// Makes the elements written to the batch available for reading.
+    ///
+    /// The batch can be used for further writes afterwards.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements published.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(4);
+    /// let mut batch = producer.batch();
+    /// batch.write_element(1);
+    /// batch.write_element(2);
+    /// assert_eq!(batch.flush(), 2);
+    /// assert_eq!(consumer.available(), 2);
+    /// ```
+    pub fn flush(&mut self) -> usize {
+        let pending = self.pending;
+        if pending != 0 {
+            self.producer.index = self.index;
+            self.producer.buffer.publish(pending);
+            self.producer.written += pending as u64;
+            self.available -= pending;
+            self.pending = 0;
+        }
+        pending
+    }
+
+    /// Returns the number of elements written to the batch since the last
+    /// flush.
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+}
+
+impl<T, I: Index> Drop for WriteBatch<'_, T, I> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<T, I: Index> fmt::Debug for WriteBatch<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteBatch")
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
@@ -0,0 +1,546 @@
+use core::{fmt, sync::atomic::fence};
+use std::{
+    sync::Mutex,
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    sync::{Arc, AtomicBool, Ordering},
+    Consumer, DirectRingBuffer, Index, Producer, WaitStrategy,
+};
+
+/// Slot in which a handle parks its thread until the other handle makes
+/// progress.
+///
+/// The handle waiting for a condition registers its thread, sets `waiting` and
+/// checks the condition again before parking, with a sequentially consistent
+/// fence in between. The handle changing a position stores it with
+/// [`PROGRESS`](crate::sync::PROGRESS) ordering and then checks `waiting` with
+/// a sequentially consistent load, so that at least one of them sees the other
+/// and no wakeup is lost. The handle making progress thus only pays for the
+/// load while no thread waits.
+///
+/// With the `parking_lot` feature, the blocking methods park the thread in the
+/// queue of `parking_lot_core` keyed by the address of the slot, which checks
+/// `waiting` again under the lock of the queue. The thread handle is then used
+/// only by [`Waiter::arm`].
+#[derive(Debug, Default)]
+pub(crate) struct WaitSlot {
+    waiting: AtomicBool,
+    thread: Mutex<Option<Thread>>,
+}
+
+impl WaitSlot {
+    /// Waits with `strategy` until `ready` returns `true` or the deadline
+    /// passes.
+    ///
+    /// Returns `false` if the deadline passed first.
+    pub(crate) fn wait_until(
+        &self,
+        mut ready: impl FnMut() -> bool,
+        deadline: Option<Instant>,
+        strategy: &dyn WaitStrategy,
+    ) -> bool {
+        let mut attempt = 0;
+        while !ready() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return false;
+            }
+            let park = strategy.wait(attempt);
+            attempt = attempt.saturating_add(1);
+            if park && !self.park(&mut ready, deadline) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Parks the current thread until it is woken or the deadline passes,
+    /// unless `ready` returns `true` once the thread is registered.
+    ///
+    /// Returns `false` if the deadline passed.
+    #[cfg(not(feature = "parking_lot"))]
+    fn park(&self, ready: &mut impl FnMut() -> bool, deadline: Option<Instant>) -> bool {
+        self.arm(thread::current());
+        if ready() {
+            self.waiting.store(false, Ordering::Relaxed);
+            return true;
+        }
+        match deadline {
+            None => thread::park(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    self.waiting.store(false, Ordering::Relaxed);
+                    return false;
+                }
+                thread::park_timeout(deadline - now);
+            }
+        }
+        true
+    }
+
+    #[cfg(feature = "parking_lot")]
+    fn park(&self, ready: &mut impl FnMut() -> bool, deadline: Option<Instant>) -> bool {
+        use parking_lot_core::{ParkResult, DEFAULT_PARK_TOKEN};
+
+        self.waiting.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        if ready() {
+            self.waiting.store(false, Ordering::Relaxed);
+            return true;
+        }
+        // The key is the address of the slot, which outlives the parked thread.
+        // The thread is not parked if it has been woken since it was registered.
+        let result = unsafe {
+            parking_lot_core::park(
+                self.key(),
+                || self.waiting.load(Ordering::Relaxed),
+                || {},
+                |_, _| {},
+                DEFAULT_PARK_TOKEN,
+                deadline,
+            )
+        };
+        if let ParkResult::TimedOut = result {
+            self.waiting.store(false, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
+    /// Registers `thread` to be unparked by the next wakeup.
+    fn arm(&self, thread: Thread) {
+        *self.thread.lock().unwrap_or_else(|e| e.into_inner()) = Some(thread);
+        self.waiting.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+    }
+
+    /// Wakes the thread waiting in the slot, if any.
+    #[inline]
+    pub(crate) fn wake(&self) {
+        if self.waiting.load(Ordering::SeqCst) && self.waiting.swap(false, Ordering::Relaxed) {
+            self.unpark();
+        }
+    }
+
+    fn unpark(&self) {
+        #[cfg(feature = "parking_lot")]
+        unsafe {
+            parking_lot_core::unpark_one(self.key(), |_| parking_lot_core::DEFAULT_UNPARK_TOKEN)
+        };
+        if let Some(thread) = self.thread.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            thread.unpark();
+        }
+    }
+
+    /// Returns the key of the `parking_lot_core` queue of the slot.
+    #[cfg(feature = "parking_lot")]
+    #[inline]
+    fn key(&self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+impl<T, I: Index> Producer<T, I> {
+    /// Creates a token for waiting for space in a custom loop.
+    ///
+    /// See [`Waiter`] for details. The token keeps the ring buffer alive, so
+    /// [`Producer::reunite`] fails while it exists.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    pub fn waiter(&self) -> Waiter<T, I> {
+        Waiter {
+            buffer: Arc::clone(&self.buffer),
+            space: true,
+        }
+    }
+
+    /// Parks the thread until at least `n` elements can be written.
+    ///
+    /// Nothing is written, so that the waiting can be combined with any of the
+    /// writing methods, such as [`Producer::grant`].
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of elements that must be writable.
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least `n` elements can be written, or `false` if the
+    /// consumer has been dropped or `n` exceeds the capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use std::io::Read;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[0; 8]);
+    /// let reader = std::thread::spawn(move || {
+    ///     consumer.advance(8);
+    ///     consumer
+    /// });
+    /// assert!(producer.wait_for_space(4));
+    /// let mut grant = producer.grant(4).unwrap();
+    /// let len = (&[1, 2, 3, 4][..]).read(&mut grant).unwrap();
+    /// grant.commit(len);
+    /// reader.join().unwrap();
+    /// ```
+    pub fn wait_for_space(&mut self, n: usize) -> bool {
+        if n > self.capacity() {
+            return false;
+        }
+        let buffer = &self.buffer;
+        buffer.space_waiter.wait_until(
+            || buffer.available_write() >= n || buffer.is_consumer_dropped(),
+            None,
+            buffer.wait_strategy(),
+        );
+        !buffer.is_consumer_dropped()
+    }
+}
+
+impl<T: Copy, I: Index> Producer<T, I> {
+    /// Writes all elements of a slice to the ring buffer, parking the thread
+    /// while the buffer is full.
+    ///
+    /// The thread is unparked by the consumer when it frees space, so no
+    /// polling is involved. The elements become readable as they are written,
+    /// in as many parts as the free space allows.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The elements to write.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written, which is less than `src.len()` only if
+    /// the consumer has been dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u32>(16);
+    /// let writer = std::thread::spawn(move || {
+    ///     let src: Vec<u32> = (0..1000).collect();
+    ///     producer.write_blocking(&src)
+    /// });
+    /// let mut dst = vec![0; 1000];
+    /// assert_eq!(consumer.read_blocking(&mut dst), 1000);
+    /// assert_eq!(writer.join().unwrap(), 1000);
+    /// assert!(dst.iter().copied().eq(0..1000));
+    /// ```
+    pub fn write_blocking(&mut self, src: &[T]) -> usize {
+        self.write_until(src, None)
+    }
+
+    /// Writes the elements of a slice to the ring buffer, parking the thread
+    /// while the buffer is full, until the timeout elapses.
+    ///
+    /// This method behaves like [`Producer::write_blocking`], but gives up
+    /// waiting for space once `timeout` has elapsed since the call, so that the
+    /// thread regains control even if the consumer stalls. The elements written
+    /// before that remain readable.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The elements to write.
+    /// * `timeout` - The maximum time to wait for space.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written before the timeout elapsed or the
+    /// consumer was dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use std::time::Duration;
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(4);
+    /// let written = producer.write_timeout(&[1, 2, 3, 4, 5, 6], Duration::from_millis(2));
+    /// assert_eq!(written, 4);
+    /// assert_eq!(consumer.available(), 4);
+    /// ```
+    pub fn write_timeout(&mut self, src: &[T], timeout: Duration) -> usize {
+        self.write_until(src, Instant::now().checked_add(timeout))
+    }
+
+    /// Writes the elements of a slice, waiting for space until the deadline.
+    fn write_until(&mut self, src: &[T], deadline: Option<Instant>) -> usize {
+        let mut written = 0;
+        loop {
+            written += self.write_from_slice(&src[written..]);
+            if written == src.len() {
+                return written;
+            }
+            let buffer = &self.buffer;
+            let ready = buffer.space_waiter.wait_until(
+                || buffer.available_write() != 0 || buffer.is_consumer_dropped(),
+                deadline,
+                buffer.wait_strategy(),
+            );
+            if !ready || buffer.is_consumer_dropped() {
+                return written;
+            }
+        }
+    }
+}
+
+impl<T, I: Index> Consumer<T, I> {
+    /// Creates a token for waiting for elements in a custom loop.
+    ///
+    /// See [`Waiter`] for details. The token keeps the ring buffer alive, so
+    /// [`Producer::reunite`] fails while it exists.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    pub fn waiter(&self) -> Waiter<T, I> {
+        Waiter {
+            buffer: Arc::clone(&self.buffer),
+            space: false,
+        }
+    }
+
+    /// Parks the thread until at least `n` elements can be read.
+    ///
+    /// Nothing is read, so that the waiting can be combined with any of the
+    /// reading methods, such as [`Consumer::read_grant`].
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of elements that must be readable.
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least `n` elements can be read, or `false` if the producer
+    /// has been dropped with fewer elements left or `n` exceeds the capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// let writer = std::thread::spawn(move || {
+    ///     producer.write_exact(&[1, 2]);
+    ///     producer.write_exact(&[3, 4]);
+    /// });
+    /// assert!(consumer.wait_for_data(4));
+    /// assert_eq!(consumer.read_array::<4>(), Some([1, 2, 3, 4]));
+    /// writer.join().unwrap();
+    /// assert!(!consumer.wait_for_data(1));
+    /// ```
+    pub fn wait_for_data(&mut self, n: usize) -> bool {
+        if n > self.capacity() {
+            return false;
+        }
+        let buffer = &self.buffer;
+        buffer.data_waiter.wait_until(
+            || buffer.available_read() >= n || buffer.is_producer_dropped(),
+            None,
+            buffer.wait_strategy(),
+        );
+        buffer.available_read() >= n
+    }
+}
+
+impl<T: Copy, I: Index> Consumer<T, I> {
+    /// Reads elements into a whole slice, parking the thread while the buffer
+    /// is empty.
+    ///
+    /// The thread is unparked by the producer when it writes elements, so no
+    /// polling is involved. The elements are consumed as they are read.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to fill.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read, which is less than `dst.len()` only if the
+    /// producer has been dropped and all the elements it wrote have been read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(16);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// drop(producer);
+    /// let mut dst = [0; 8];
+    /// assert_eq!(consumer.read_blocking(&mut dst), 3);
+    /// assert_eq!(dst[..3], [1, 2, 3]);
+    /// ```
+    pub fn read_blocking(&mut self, dst: &mut [T]) -> usize {
+        self.read_until(dst, None)
+    }
+
+    /// Reads elements into a slice, parking the thread while the buffer is
+    /// empty, until the timeout elapses.
+    ///
+    /// This method behaves like [`Consumer::read_blocking`], but gives up
+    /// waiting for elements once `timeout` has elapsed since the call, so that
+    /// the thread regains control even if the producer stalls.
+    ///
+    /// This method is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to fill.
+    /// * `timeout` - The maximum time to wait for elements.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read before the timeout elapsed or the producer
+    /// was dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use std::time::Duration;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2]);
+    /// let mut dst = [0; 4];
+    /// assert_eq!(consumer.read_timeout(&mut dst, Duration::from_millis(2)), 2);
+    /// assert_eq!(dst, [1, 2, 0, 0]);
+    /// ```
+    pub fn read_timeout(&mut self, dst: &mut [T], timeout: Duration) -> usize {
+        self.read_until(dst, Instant::now().checked_add(timeout))
+    }
+
+    /// Reads elements into a slice, waiting for elements until the deadline.
+    fn read_until(&mut self, dst: &mut [T], deadline: Option<Instant>) -> usize {
+        let mut read = 0;
+        loop {
+            read += self.read_into_slice(&mut dst[read..]);
+            if read == dst.len() {
+                return read;
+            }
+            let buffer = &self.buffer;
+            buffer.data_waiter.wait_until(
+                || buffer.available_read() != 0 || buffer.is_producer_dropped(),
+                deadline,
+                buffer.wait_strategy(),
+            );
+            if buffer.available_read() == 0 {
+                return read;
+            }
+        }
+    }
+}
+
+/// Token for waiting for the other handle in a custom loop.
+///
+/// This struct is created by [`Producer::waiter`] and [`Consumer::waiter`]. It
+/// lets a thread that runs its own loop, and parks with
+/// [`std::thread::park`] when it has nothing to do, be unparked when the other
+/// handle frees space or writes elements, without using the blocking methods.
+/// Since only [`std::thread::park`] is involved, several ring buffers and other
+/// sources of wakeups can be waited for at once.
+///
+/// The registration made by [`Waiter::arm`] is consumed by the first wakeup, so
+/// the loop arms the waiter, checks whether it can make progress, and parks only
+/// if it cannot. Checking after arming ensures that no progress made in between
+/// is missed. The registration is kept apart from that of the blocking methods
+/// of the handle, so that a thread arming the waiter does not keep a thread
+/// blocked in one of them from being unparked. The waiters of the same handle
+/// share the registration, though.
+///
+/// This struct is available only with the `std-blocking` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use std::thread;
+///
+/// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+/// let waiter = consumer.waiter();
+/// let writer = thread::spawn(move || {
+///     for i in 0..10 {
+///         producer.write_blocking(&[i]);
+///     }
+/// });
+/// let mut received = Vec::new();
+/// while received.len() < 10 {
+///     waiter.arm();
+///     match consumer.read_element() {
+///         Some(value) => received.push(value),
+///         None => thread::park(),
+///     }
+/// }
+/// writer.join().unwrap();
+/// assert_eq!(received, (0..10).collect::<Vec<_>>());
+/// ```
+///
+/// The waiter can be sent to another thread only if the elements can be sent,
+/// since it may drop them there as the last owner of the ring buffer.
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (_producer, consumer) = create_ring_buffer::<std::rc::Rc<u8>>(1);
+/// let waiter = consumer.waiter();
+/// std::thread::spawn(move || drop(waiter));
+/// ```
+pub struct Waiter<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
+    /// `true` if the waiter waits for space, `false` for elements.
+    space: bool,
+}
+
+impl<T, I: Index> Waiter<T, I> {
+    /// Registers the current thread to be unparked the next time the other
+    /// handle makes progress or is dropped.
+    pub fn arm(&self) {
+        self.arm_thread(thread::current());
+    }
+
+    /// Registers a thread to be unparked the next time the other handle makes
+    /// progress or is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `thread` - The thread to unpark, for example the thread running an
+    ///   event loop on behalf of the handle.
+    pub fn arm_thread(&self, thread: Thread) {
+        let slot = if self.space {
+            &self.buffer.space_armed
+        } else {
+            &self.buffer.data_armed
+        };
+        slot.arm(thread);
+    }
+}
+
+impl<T, I: Index> fmt::Debug for Waiter<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Waiter")
+            .field("space", &self.space)
+            .finish()
+    }
+}
+
+// The waiter only accesses the elements if it is the last owner of the ring
+// buffer, and then drops the unread ones, as an `Arc` of them would.
+unsafe impl<T: Send, I: Index> Send for Waiter<T, I> {}
+unsafe impl<T: Send + Sync, I: Index> Sync for Waiter<T, I> {}
@@ -0,0 +1,604 @@
+use alloc::{alloc::handle_alloc_error, vec::Vec};
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ptr,
+};
+
+#[cfg(feature = "std-blocking")]
+use std::sync::Arc;
+
+use crate::{
+    split_ring_buffer, Consumer, CreateError, DirectRingBuffer, Index, OverwriteConsumer, Producer,
+};
+#[cfg(feature = "std-blocking")]
+use crate::WaitStrategy;
+
+/// Size of a transparent huge page on most targets.
+#[cfg(feature = "huge-pages")]
+const HUGE_PAGE_SIZE: usize = 2 << 20;
+
+/// Builder for configuring and creating a ring buffer.
+///
+/// The options compose, so that a ring buffer can be created with any
+/// combination of them. [`create_ring_buffer`](crate::create_ring_buffer) is
+/// equivalent to a builder with only the capacity set.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::RingBufferBuilder;
+///
+/// let (producer, consumer) = RingBufferBuilder::<u8>::new()
+///     .capacity(1024)
+///     .zeroed()
+///     .build();
+/// assert_eq!(producer.capacity(), 1024);
+/// assert_eq!(consumer.available(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RingBufferBuilder<T, I: Index = usize> {
+    capacity: usize,
+    power_of_two: bool,
+    align: usize,
+    #[cfg(feature = "huge-pages")]
+    huge_pages: bool,
+    #[cfg(all(feature = "numa", target_os = "linux"))]
+    numa_node: Option<u32>,
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    double_mapped: bool,
+    zeroed: bool,
+    overwrite: bool,
+    #[cfg(feature = "zeroize")]
+    zeroize: Option<fn(&mut T)>,
+    #[cfg(feature = "std-blocking")]
+    wait_strategy: Option<Arc<dyn WaitStrategy>>,
+    _marker: PhantomData<(T, I)>,
+}
+
+impl<T> RingBufferBuilder<T> {
+    /// Creates a builder with a capacity of zero and no other options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, I: Index> RingBufferBuilder<T, I> {
+    /// Sets the type of the counters shared by the producer and the consumer.
+    ///
+    /// By default, the counters are `usize`. A narrower [`Index`] type shrinks
+    /// the shared state and allows targets without atomics of the native width,
+    /// but limits the capacity to [`Index::MAX_CAPACITY`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{CreateError, RingBufferBuilder};
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .index::<u16>()
+    ///     .capacity(1024)
+    ///     .build();
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_array::<3>(), Some([1, 2, 3]));
+    ///
+    /// let result = RingBufferBuilder::<u8>::new()
+    ///     .index::<u16>()
+    ///     .capacity(65536)
+    ///     .try_build();
+    /// assert_eq!(result.unwrap_err(), CreateError::CapacityOverflow);
+    /// ```
+    pub fn index<J: Index>(self) -> RingBufferBuilder<T, J> {
+        RingBufferBuilder {
+            capacity: self.capacity,
+            power_of_two: self.power_of_two,
+            align: self.align,
+            #[cfg(feature = "huge-pages")]
+            huge_pages: self.huge_pages,
+            #[cfg(all(feature = "numa", target_os = "linux"))]
+            numa_node: self.numa_node,
+            #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+            double_mapped: self.double_mapped,
+            zeroed: self.zeroed,
+            overwrite: self.overwrite,
+            #[cfg(feature = "zeroize")]
+            zeroize: self.zeroize,
+            #[cfg(feature = "std-blocking")]
+            wait_strategy: self.wait_strategy,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the capacity of the ring buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of elements the ring buffer can hold.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Rounds the capacity up to the next power of two.
+    ///
+    /// The indices of a ring buffer whose capacity is a power of two wrap
+    /// around with a bitmask instead of a comparison, which speeds up
+    /// element-at-a-time operations. This applies to any ring buffer created
+    /// with such a capacity; this option only makes sure of it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (producer, _) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(1000)
+    ///     .power_of_two()
+    ///     .build();
+    /// assert_eq!(producer.capacity(), 1024);
+    /// ```
+    pub fn power_of_two(mut self) -> Self {
+        self.power_of_two = true;
+        self
+    }
+
+    /// Sets the minimum alignment of the storage in bytes.
+    ///
+    /// By default, the storage is aligned for `T` only. A larger alignment
+    /// allows the storage, obtained through [`Producer::as_mut_ptr`] or
+    /// [`Consumer::as_ptr`], to be used directly as a SIMD or DMA target. An
+    /// alignment smaller than that of `T` has no effect. The storage of an
+    /// over-aligned ring buffer is not reused by [`Producer::reunite`], which
+    /// moves the unread elements to a new `Vec` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `align` - The minimum alignment in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (producer, _) = RingBufferBuilder::<f32>::new()
+    ///     .capacity(1024)
+    ///     .align(64)
+    ///     .build();
+    /// assert_eq!(producer.as_mut_ptr() as usize % 64, 0);
+    /// ```
+    pub fn align(mut self, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self.align = align;
+        self
+    }
+
+    /// Backs the storage with transparent huge pages where supported.
+    ///
+    /// The storage is aligned to 2 MiB, the size of a huge page on x86_64 and
+    /// most other targets, and on Linux the kernel is advised with
+    /// `madvise(MADV_HUGEPAGE)` to back it with huge pages, which reduces TLB
+    /// misses when streaming through a large buffer. The advice takes effect
+    /// only if transparent huge pages are enabled in `madvise` or `always`
+    /// mode. Since the whole storage is rounded up to huge pages, this option
+    /// is intended for buffers of many megabytes. On other targets, only the
+    /// alignment is applied.
+    ///
+    /// This option is available only with the `huge-pages` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (producer, _) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(64 << 20)
+    ///     .huge_pages()
+    ///     .build();
+    /// assert_eq!(producer.as_mut_ptr() as usize % (2 << 20), 0);
+    /// ```
+    #[cfg(feature = "huge-pages")]
+    pub fn huge_pages(mut self) -> Self {
+        self.huge_pages = true;
+        self
+    }
+
+    /// Places the storage on a NUMA node.
+    ///
+    /// The storage is aligned to the page size, and the kernel is asked with
+    /// `mbind(MPOL_PREFERRED)` to allocate its pages on `node`, moving the pages
+    /// that are already allocated. Placing the storage on the node the producer
+    /// and the consumer are pinned to avoids cross-node memory traffic. The
+    /// placement is a preference: if `node` does not exist or has no free
+    /// memory, the pages are allocated elsewhere.
+    ///
+    /// This option is available only with the `numa` feature on Linux.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The NUMA node to allocate the storage on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(1 << 20)
+    ///     .numa_node(0)
+    ///     .build();
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_array::<3>(), Some([1, 2, 3]));
+    /// ```
+    #[cfg(all(feature = "numa", target_os = "linux"))]
+    pub fn numa_node(mut self, node: u32) -> Self {
+        self.numa_node = Some(node);
+        self
+    }
+
+    /// Maps the storage twice back-to-back in virtual memory.
+    ///
+    /// The elements past the end of the storage are the ones at its beginning,
+    /// so the readable and the writable elements are always a single contiguous
+    /// slice: the second slice returned by [`Consumer::as_slices`] and similar
+    /// methods is always empty, and the closures passed to
+    /// [`Producer::write_slices`] and [`Consumer::read_slices`] are called
+    /// once. The regions returned by [`Producer::writable_regions`] and
+    /// [`Consumer::readable_regions`] may extend past the capacity into the
+    /// second mapping.
+    ///
+    /// The size of each mapping must be a multiple of the page size, so the
+    /// capacity is rounded up to the next multiple of the number of elements
+    /// fitting in a page, after [`RingBufferBuilder::power_of_two`] is applied.
+    /// The storage is zeroed, page-aligned, and backed by a `memfd_create`
+    /// file. Like an over-aligned storage, it is not reused by
+    /// [`Producer::reunite`].
+    ///
+    /// This option is available only with the `double-mapped` feature on
+    /// Linux.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(4096)
+    ///     .double_mapped()
+    ///     .build();
+    /// let capacity = producer.capacity();
+    /// producer.write_exact(&vec![0; capacity - 2]);
+    /// consumer.advance(capacity - 2);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// assert_eq!(consumer.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+    /// ```
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    pub fn double_mapped(mut self) -> Self {
+        self.double_mapped = true;
+        self
+    }
+
+    /// Fills the storage memory with zero bytes when it is allocated.
+    ///
+    /// By default, the storage is left as returned by the allocator and may
+    /// contain recycled heap contents, which are observable through
+    /// [`Producer::vacant_slices_uninit`]. This option clears them. It does not make the zeroed
+    /// memory a valid value of `T`.
+    pub fn zeroed(mut self) -> Self {
+        self.zeroed = true;
+        self
+    }
+
+    /// Scrubs the elements when they are released and the whole storage when
+    /// the ring buffer is dropped.
+    ///
+    /// The elements are zeroized with [`Zeroize`](zeroize::Zeroize) before they
+    /// are dropped, whether they are read through slices, discarded or still
+    /// unread when the ring buffer is dropped, and their slots are overwritten
+    /// with zero bytes afterwards. Slots of elements moved out by
+    /// [`Consumer::read_element`] are overwritten with zero bytes as well, so
+    /// that no copies are left behind in the freed heap memory.
+    ///
+    /// Since the consumer writes to the released slots, an
+    /// [`Observer`](crate::Observer) cannot be created, and both writable slices
+    /// are filled with default values each time they are handed out as
+    /// `&mut [T]`.
+    ///
+    /// This option is available only with the `zeroize` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(4)
+    ///     .zeroize()
+    ///     .build();
+    /// producer.write_exact(&[0x5a; 4]);
+    /// assert_eq!(consumer.advance(4), 4);
+    /// let (first, _) = producer.vacant_slices_uninit();
+    /// assert!(first.iter().all(|e| unsafe { e.assume_init() } == 0));
+    /// ```
+    #[cfg(feature = "zeroize")]
+    pub fn zeroize(mut self) -> Self
+    where
+        T: zeroize::Zeroize,
+    {
+        self.zeroize = Some(T::zeroize);
+        self
+    }
+
+    /// Sets the strategy of the handles waiting for each other.
+    ///
+    /// The strategy is used by the blocking methods, such as
+    /// [`Producer::write_blocking`], while the other handle has not made enough
+    /// progress. By default, the thread is parked right away with [`Park`](crate::Park).
+    ///
+    /// This option is available only with the `std-blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The strategy, such as [`BusySpin`](crate::BusySpin),
+    ///   [`SpinThenYield`](crate::SpinThenYield) or [`Backoff`](crate::Backoff).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{BusySpin, RingBufferBuilder};
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u64>::new()
+    ///     .capacity(64)
+    ///     .wait_strategy(BusySpin)
+    ///     .build();
+    /// let writer = std::thread::spawn(move || producer.write_blocking(&[7; 1000]));
+    /// let mut dst = [0; 1000];
+    /// assert_eq!(consumer.read_blocking(&mut dst), 1000);
+    /// writer.join().unwrap();
+    /// ```
+    #[cfg(feature = "std-blocking")]
+    pub fn wait_strategy(mut self, strategy: impl WaitStrategy + 'static) -> Self {
+        self.wait_strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Creates the ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a `Producer<T>` and a `Consumer<T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity exceeds the maximum size of an allocation or the
+    /// maximum capacity of the index type.
+    pub fn build(self) -> (Producer<T, I>, Consumer<T, I>) {
+        let capacity = self.allocated_capacity().expect("capacity overflow");
+        assert!(
+            capacity <= I::MAX_CAPACITY,
+            "capacity exceeds the maximum capacity of the index type"
+        );
+        let align = self.storage_align();
+        #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+        if self.double_mapped {
+            let buffer = match DirectRingBuffer::double_mapped(capacity, align) {
+                Ok(buffer) => buffer,
+                Err(CreateError::AllocationFailed) => panic!("failed to map the storage"),
+                Err(_) => panic!("capacity overflow"),
+            };
+            return self.split(buffer);
+        }
+        if align > mem::align_of::<T>() {
+            let buffer = match DirectRingBuffer::with_alignment(capacity, align, self.zeroed) {
+                Ok(buffer) => buffer,
+                Err(CreateError::AllocationFailed) => handle_alloc_error(
+                    DirectRingBuffer::<T, I>::storage_layout(capacity, align).unwrap(),
+                ),
+                Err(_) => panic!("capacity overflow"),
+            };
+            return self.split(buffer);
+        }
+        let vec = Vec::with_capacity(capacity);
+        self.split_vec(vec, capacity)
+    }
+
+    /// Creates the ring buffer, reporting failures as errors.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a tuple of a `Producer<T>` and a `Consumer<T>`, or a
+    /// [`CreateError`] describing why the ring buffer could not be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{CreateError, RingBufferBuilder};
+    ///
+    /// let result = RingBufferBuilder::<u8>::new().try_build();
+    /// assert_eq!(result.unwrap_err(), CreateError::ZeroCapacity);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn try_build(self) -> Result<(Producer<T, I>, Consumer<T, I>), CreateError> {
+        if self.capacity == 0 {
+            return Err(CreateError::ZeroCapacity);
+        }
+        let capacity = match self.allocated_capacity() {
+            Some(capacity)
+                if capacity <= I::MAX_CAPACITY && Layout::array::<T>(capacity).is_ok() =>
+            {
+                capacity
+            }
+            _ => return Err(CreateError::CapacityOverflow),
+        };
+        let align = self.storage_align();
+        #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+        if self.double_mapped {
+            let buffer = DirectRingBuffer::double_mapped(capacity, align)?;
+            return Ok(self.split(buffer));
+        }
+        if align > mem::align_of::<T>() {
+            let buffer = DirectRingBuffer::with_alignment(capacity, align, self.zeroed)?;
+            return Ok(self.split(buffer));
+        }
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(capacity)
+            .map_err(|_| CreateError::AllocationFailed)?;
+        Ok(self.split_vec(vec, capacity))
+    }
+
+    /// Creates a ring buffer whose producer overwrites the oldest elements when
+    /// the buffer is full.
+    ///
+    /// The element-wise and slice-copying writes of the producer, such as
+    /// [`Producer::write_element`] and [`Producer::write_from_slice`], evict
+    /// the oldest unread elements to make room instead of waiting for the
+    /// consumer, so the producer never stalls. The writes that hand out the
+    /// vacant space itself only use the space that is free. The consumer is an
+    /// [`OverwriteConsumer`], which copies the elements out and discards them
+    /// if they were evicted in the meantime.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a `Producer<T>` and an `OverwriteConsumer<T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity exceeds the maximum size of an allocation or the
+    /// maximum capacity of the index type, or if the storage is zeroized, since
+    /// the evicted elements cannot be scrubbed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(3)
+    ///     .build_overwriting();
+    /// producer.write_from_slice(&[1, 2]);
+    /// producer.write_from_slice(&[3, 4, 5]);
+    /// let mut dst = [0; 3];
+    /// assert_eq!(consumer.read_into_slice(&mut dst), 3);
+    /// assert_eq!(dst, [3, 4, 5]);
+    /// assert_eq!(consumer.dropped(), 2);
+    /// ```
+    pub fn build_overwriting(mut self) -> (Producer<T, I>, OverwriteConsumer<T, I>)
+    where
+        T: Copy,
+    {
+        #[cfg(feature = "zeroize")]
+        assert!(
+            self.zeroize.is_none(),
+            "an overwriting ring buffer cannot be zeroized"
+        );
+        self.overwrite = true;
+        let (producer, consumer) = self.build();
+        (producer, OverwriteConsumer::new(consumer))
+    }
+
+    /// Returns the capacity to allocate, or `None` if rounding it up overflows.
+    fn allocated_capacity(&self) -> Option<usize> {
+        let capacity = if self.power_of_two && self.capacity != 0 {
+            self.capacity.checked_next_power_of_two()?
+        } else {
+            self.capacity
+        };
+        #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+        if self.double_mapped && mem::size_of::<T>() != 0 {
+            // The smallest number of elements whose size is a multiple of the
+            // page size.
+            let page = crate::page_size();
+            let unit = page / gcd(page, mem::size_of::<T>());
+            return capacity.checked_next_multiple_of(unit);
+        }
+        Some(capacity)
+    }
+
+    /// Returns the minimum alignment of the storage.
+    fn storage_align(&self) -> usize {
+        #[allow(unused_mut)]
+        let mut align = self.align;
+        #[cfg(feature = "huge-pages")]
+        if self.huge_pages {
+            align = align.max(HUGE_PAGE_SIZE);
+        }
+        #[cfg(all(feature = "numa", target_os = "linux"))]
+        if self.numa_node.is_some() {
+            align = align.max(crate::page_size());
+        }
+        align
+    }
+
+    /// Initializes the allocated storage and splits it into the handles.
+    fn split_vec(
+        self,
+        mut vec: Vec<MaybeUninit<T>>,
+        capacity: usize,
+    ) -> (Producer<T, I>, Consumer<T, I>) {
+        if self.zeroed {
+            unsafe { ptr::write_bytes(vec.as_mut_ptr(), 0, capacity) };
+        }
+        // `MaybeUninit<T>` does not need to be initialized.
+        unsafe { vec.set_len(capacity) };
+        self.split(DirectRingBuffer::new(vec.into_boxed_slice(), 0))
+    }
+
+    /// Applies the options to the buffer and splits it into the handles.
+    fn split(self, buffer: DirectRingBuffer<T, I>) -> (Producer<T, I>, Consumer<T, I>) {
+        let mut buffer = buffer;
+        #[cfg(feature = "huge-pages")]
+        if self.huge_pages {
+            buffer.advise_huge_pages();
+        }
+        #[cfg(all(feature = "numa", target_os = "linux"))]
+        if let Some(node) = self.numa_node {
+            buffer.bind_numa_node(node);
+        }
+        #[cfg(feature = "zeroize")]
+        {
+            buffer.zeroize = self.zeroize;
+        }
+        #[cfg(feature = "std-blocking")]
+        {
+            buffer.wait_strategy = self.wait_strategy;
+        }
+        buffer.overwrite = self.overwrite;
+        split_ring_buffer(buffer)
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+#[cfg(all(feature = "double-mapped", target_os = "linux"))]
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl<T, I: Index> Default for RingBufferBuilder<T, I> {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            power_of_two: false,
+            align: 1,
+            #[cfg(feature = "huge-pages")]
+            huge_pages: false,
+            #[cfg(all(feature = "numa", target_os = "linux"))]
+            numa_node: None,
+            #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+            double_mapped: false,
+            zeroed: false,
+            overwrite: false,
+            #[cfg(feature = "zeroize")]
+            zeroize: None,
+            #[cfg(feature = "std-blocking")]
+            wait_strategy: None,
+            _marker: PhantomData,
+        }
+    }
+}
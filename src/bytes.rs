@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Consumer, Index, Producer};
+
+impl<I: Index> Consumer<u8, I> {
+    /// Reads the bytes up to and including the next occurrence of a delimiter.
+    ///
+    /// This method scans the readable bytes for `delimiter`, across the
+    /// wrap-around point of the buffer, and reads them up to and including it.
+    /// If the delimiter is not found, nothing is read, so that a line-oriented
+    /// parser can call this method again once more bytes have been written.
+    ///
+    /// The method is not named `read_until` so that it does not shadow
+    /// `std::io::BufRead::read_until`.
+    ///
+    /// # Arguments
+    ///
+    /// * `delimiter` - The byte ending the bytes to read.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` containing the bytes read, ending with `delimiter`, or `None` if
+    /// the readable bytes do not contain `delimiter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(16);
+    /// producer.write_exact(b"GET /\r\nHo");
+    /// assert_eq!(consumer.read_until_delimiter(b'\n'), Some(b"GET /\r\n".to_vec()));
+    /// assert_eq!(consumer.read_until_delimiter(b'\n'), None);
+    /// producer.write_exact(b"st: a\r\n");
+    /// assert_eq!(consumer.read_until_delimiter(b'\n'), Some(b"Host: a\r\n".to_vec()));
+    /// ```
+    pub fn read_until_delimiter(&mut self, delimiter: u8) -> Option<Vec<u8>> {
+        let (first, second) = self.as_slices();
+        let len = match first.iter().position(|&b| b == delimiter) {
+            Some(pos) => pos + 1,
+            None => first.len() + second.iter().position(|&b| b == delimiter)? + 1,
+        };
+        let mut bytes = Vec::with_capacity(len);
+        bytes.extend_from_slice(&first[..len.min(first.len())]);
+        bytes.extend_from_slice(&second[..len.saturating_sub(first.len())]);
+        self.advance(len);
+        Some(bytes)
+    }
+}
+
+/// Counts the bytes of formatted output without storing them.
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Writes strings to the ring buffer as UTF-8 bytes, without allocating.
+///
+/// Each string is written whole or not at all: if it does not fit into the
+/// free space, nothing of it is written and [`fmt::Error`] is returned. A
+/// `write!` through this trait may thus leave the pieces before the failing
+/// one written. [`Producer::write_fmt`], which `write!` calls when the trait is
+/// not imported, writes the whole output or nothing instead.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use std::fmt::Write;
+///
+/// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+/// producer.write_str("level").unwrap();
+/// assert!(producer.write_str("=42\n").is_err()); // Not enough space
+/// assert_eq!(consumer.read_array::<5>(), Some(*b"level"));
+/// ```
+impl<I: Index> fmt::Write for Producer<u8, I> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.write_exact(s.as_bytes()) {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+impl<I: Index> Producer<u8, I> {
+    /// Writes formatted output to the ring buffer, or nothing at all.
+    ///
+    /// This method is called by the `write!` and `writeln!` macros. The output
+    /// is formatted a first time to measure it, and written only if it fits
+    /// into the free space, so that the consumer never observes a truncated
+    /// message. Nothing is allocated, which makes the method usable for
+    /// logging from real-time threads. The arguments are expected to format to
+    /// the same output both times.
+    ///
+    /// Being an inherent method, it takes precedence over the `write_fmt`
+    /// methods of [`fmt::Write`] and `std::io::Write`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The formatted output, as created by `format_args!`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the whole output was written, or [`fmt::Error`] if it did not
+    /// fit into the free space, in which case nothing was written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// write!(producer, "x={}", 42).unwrap();
+    /// assert!(write!(producer, "y={}", 1234).is_err()); // Not enough space
+    /// assert_eq!(consumer.read_array::<4>(), Some(*b"x=42"));
+    /// ```
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        if let Some(s) = args.as_str() {
+            return fmt::Write::write_str(self, s);
+        }
+        let mut counter = Counter(0);
+        fmt::write(&mut counter, args)?;
+        if self.available_cached(counter.0) < counter.0 {
+            return Err(fmt::Error);
+        }
+        fmt::write(self, args)
+    }
+}
+
+macro_rules! impl_endian {
+    ($($t:ident: $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident;)*) => {
+        impl<I: Index> Consumer<u8, I> {
+            $(
+                impl_endian!(@read $t, $read_le, from_le_bytes, "little", $write_le);
+                impl_endian!(@read $t, $read_be, from_be_bytes, "big", $write_be);
+            )*
+        }
+
+        impl<I: Index> Producer<u8, I> {
+            $(
+                impl_endian!(@write $t, $write_le, to_le_bytes, "little", $read_le);
+                impl_endian!(@write $t, $write_be, to_be_bytes, "big", $read_be);
+            )*
+        }
+    };
+    (@read $t:ident, $read:ident, $from:ident, $endian:literal, $write:ident) => {
+        #[doc = concat!("Reads a ", $endian, "-endian `", stringify!($t), "` from the ring buffer.")]
+        ///
+        /// The bytes are read only if all of them are available, and may wrap
+        /// around the end of the buffer.
+        ///
+        /// # Returns
+        ///
+        /// An `Option` containing the value, or `None` if fewer bytes are
+        /// available, in which case nothing is read.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use direct_ring_buffer::create_ring_buffer;
+        ///
+        /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(16);
+        #[doc = concat!("assert!(producer.", stringify!($write), "(42 as ", stringify!($t), "));")]
+        #[doc = concat!("assert_eq!(consumer.", stringify!($read), "(), Some(42 as ", stringify!($t), "));")]
+        #[doc = concat!("assert_eq!(consumer.", stringify!($read), "(), None);")]
+        /// ```
+        pub fn $read(&mut self) -> Option<$t> {
+            self.read_array().map($t::$from)
+        }
+    };
+    (@write $t:ident, $write:ident, $to:ident, $endian:literal, $read:ident) => {
+        #[doc = concat!("Writes a ", $endian, "-endian `", stringify!($t), "` to the ring buffer.")]
+        ///
+        /// The bytes are written only if all of them fit into the free space,
+        /// and may wrap around the end of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `value` - The value to write.
+        ///
+        /// # Returns
+        ///
+        /// `true` if the value was written, `false` if there was not enough
+        /// space available, in which case nothing is written.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use direct_ring_buffer::create_ring_buffer;
+        ///
+        /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(16);
+        #[doc = concat!("assert!(producer.", stringify!($write), "(42 as ", stringify!($t), "));")]
+        #[doc = concat!("assert_eq!(consumer.", stringify!($read), "(), Some(42 as ", stringify!($t), "));")]
+        /// ```
+        pub fn $write(&mut self, value: $t) -> bool {
+            self.write_exact(&value.$to())
+        }
+    };
+}
+
+impl_endian! {
+    u16: read_u16_le, read_u16_be, write_u16_le, write_u16_be;
+    u32: read_u32_le, read_u32_be, write_u32_le, write_u32_be;
+    u64: read_u64_le, read_u64_be, write_u64_le, write_u64_be;
+    u128: read_u128_le, read_u128_be, write_u128_le, write_u128_be;
+    i16: read_i16_le, read_i16_be, write_i16_le, write_i16_be;
+    i32: read_i32_le, read_i32_be, write_i32_le, write_i32_be;
+    i64: read_i64_le, read_i64_be, write_i64_le, write_i64_be;
+    i128: read_i128_le, read_i128_be, write_i128_le, write_i128_be;
+    f32: read_f32_le, read_f32_be, write_f32_le, write_f32_be;
+    f64: read_f64_le, read_f64_be, write_f64_le, write_f64_be;
+}
@@ -0,0 +1,85 @@
+use core::{error::Error, fmt};
+
+use crate::{Consumer, Index, Producer};
+
+/// Error returned when a ring buffer cannot be created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CreateError {
+    /// The requested capacity is zero.
+    ZeroCapacity,
+    /// The requested capacity exceeds the maximum size of an allocation or the
+    /// maximum capacity of the index type.
+    CapacityOverflow,
+    /// The allocator failed to allocate the storage.
+    AllocationFailed,
+}
+
+impl fmt::Display for CreateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CreateError::ZeroCapacity => "ring buffer capacity is zero",
+            CreateError::CapacityOverflow => "ring buffer capacity overflow",
+            CreateError::AllocationFailed => "ring buffer allocation failed",
+        })
+    }
+}
+
+impl Error for CreateError {}
+
+/// Error returned by [`Producer::reunite`] when the handles cannot be reunited.
+///
+/// It gives back the producer and the consumer that were passed in.
+pub struct ReuniteError<T, I: Index = usize>(pub Producer<T, I>, pub Consumer<T, I>);
+
+impl<T, I: Index> fmt::Debug for ReuniteError<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReuniteError")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
+    }
+}
+
+impl<T, I: Index> fmt::Display for ReuniteError<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("tried to reunite handles that do not own the whole ring buffer")
+    }
+}
+
+impl<T, I: Index> Error for ReuniteError<T, I> {}
+
+/// Error returned when the other handle of the ring buffer has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closed;
+
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the other handle of the ring buffer has been dropped")
+    }
+}
+
+impl Error for Closed {}
+
+/// Error returned by [`Consumer::try_read_element`] and
+/// [`Consumer::try_read_slices`] when no element can be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReadError {
+    /// The ring buffer is empty for now, but the producer may write more
+    /// elements.
+    Empty,
+    /// The ring buffer is empty and the producer has been dropped, so no
+    /// element will ever come.
+    Closed,
+}
+
+impl fmt::Display for TryReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TryReadError::Empty => "the ring buffer is empty",
+            TryReadError::Closed => "the ring buffer is empty and the producer has been dropped",
+        })
+    }
+}
+
+impl Error for TryReadError {}
@@ -0,0 +1,153 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+use crate::{Consumer, Index, Producer};
+
+/// Grant of a contiguous writable region of the ring buffer.
+///
+/// This struct is created by [`Producer::grant`]. It dereferences to the
+/// writable region as `&mut [T]`. The written elements become readable only
+/// after calling [`WriteGrant::commit`]; dropping the grant without committing
+/// writes nothing.
+pub struct WriteGrant<'a, T, I: Index = usize> {
+    producer: &'a mut Producer<T, I>,
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<'a, T: Copy + Default, I: Index> WriteGrant<'a, T, I> {
+    /// Creates a grant of up to `max` elements at the current write position.
+    pub(crate) fn new(producer: &'a mut Producer<T, I>, max: usize) -> Option<Self> {
+        let (first, _) = producer.vacant_slices_mut();
+        let len = first.len().min(max);
+        if len == 0 {
+            return None;
+        }
+        let ptr = first.as_mut_ptr();
+        Some(Self { producer, ptr, len })
+    }
+
+    /// Makes the written elements available for reading and releases the grant.
+    ///
+    /// # Arguments
+    ///
+    /// * `written` - The number of elements written from the start of the
+    ///   granted region. Values larger than the length of the region are
+    ///   clamped.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements committed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(5);
+    /// let mut grant = producer.grant(3).unwrap();
+    /// grant[..2].copy_from_slice(&[1, 2]);
+    /// assert_eq!(grant.commit(2), 2);
+    /// assert_eq!(consumer.available(), 2);
+    /// ```
+    pub fn commit(self, written: usize) -> usize {
+        let written = written.min(self.len);
+        self.producer.commit(written)
+    }
+}
+
+impl<T, I: Index> Deref for WriteGrant<'_, T, I> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // The region is reserved for this grant until it is released.
+        unsafe { from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T, I: Index> DerefMut for WriteGrant<'_, T, I> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // The region is reserved for this grant until it is released.
+        unsafe { from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T, I: Index> fmt::Debug for WriteGrant<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteGrant")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Grant of a contiguous readable region of the ring buffer.
+///
+/// This struct is created by [`Consumer::read_grant`]. It dereferences to the
+/// readable region as `&[T]`. The elements are consumed only after calling
+/// [`ReadGrant::release`]; dropping the grant without releasing consumes
+/// nothing.
+pub struct ReadGrant<'a, T, I: Index = usize> {
+    consumer: &'a mut Consumer<T, I>,
+    ptr: *const T,
+    len: usize,
+}
+
+impl<'a, T, I: Index> ReadGrant<'a, T, I> {
+    /// Creates a grant of up to `max` elements at the current read position.
+    pub(crate) fn new(consumer: &'a mut Consumer<T, I>, max: usize) -> Option<Self> {
+        let (first, _) = consumer.as_slices();
+        let len = first.len().min(max);
+        if len == 0 {
+            return None;
+        }
+        let ptr = first.as_ptr();
+        Some(Self { consumer, ptr, len })
+    }
+
+    /// Consumes the read elements and releases the grant.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumed` - The number of elements consumed from the start of the
+    ///   granted region. Values larger than the length of the region are
+    ///   clamped.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// let grant = consumer.read_grant(5).unwrap();
+    /// assert_eq!(&*grant, &[1, 2, 3]);
+    /// assert_eq!(grant.release(2), 2);
+    /// assert_eq!(consumer.available(), 1);
+    /// ```
+    pub fn release(self, consumed: usize) -> usize {
+        let consumed = consumed.min(self.len);
+        self.consumer.advance(consumed)
+    }
+}
+
+impl<T, I: Index> Deref for ReadGrant<'_, T, I> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // The region is reserved for this grant until it is released.
+        unsafe { from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T, I: Index> fmt::Debug for ReadGrant<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadGrant").field("len", &self.len).finish()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::sync::{AtomicU16, AtomicU32, AtomicUsize, Ordering};
+
+/// Unsigned integer type of the counters shared by the producer and the
+/// consumer.
+///
+/// The capacity of a ring buffer is limited to the maximum value of its index
+/// type. A type narrower than `usize` shrinks the shared state, and matches the
+/// atomics supported by small targets. This trait is sealed and implemented for
+/// `u16`, `u32` and `usize`.
+pub trait Index: private::Sealed + 'static {
+    /// The maximum capacity of a ring buffer using this index type.
+    const MAX_CAPACITY: usize;
+}
+
+mod private {
+    use crate::sync::Ordering;
+
+    pub trait Sealed {
+        type Atomic;
+
+        fn new(value: usize) -> Self::Atomic;
+        fn load(atomic: &Self::Atomic, order: Ordering) -> usize;
+        fn store(atomic: &Self::Atomic, value: usize, order: Ordering);
+        fn wrapping_sub(end: usize, start: usize) -> usize;
+        fn compare_exchange(
+            atomic: &Self::Atomic,
+            current: usize,
+            new: usize,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<usize, usize>;
+    }
+}
+
+macro_rules! impl_index {
+    ($($t:ty => $atomic:ty),* $(,)?) => {
+        $(
+            impl Index for $t {
+                const MAX_CAPACITY: usize = <$t>::MAX as usize;
+            }
+
+            impl private::Sealed for $t {
+                type Atomic = $atomic;
+
+                #[inline]
+                fn new(value: usize) -> Self::Atomic {
+                    <$atomic>::new(value as $t)
+                }
+
+                #[inline]
+                fn load(atomic: &Self::Atomic, order: Ordering) -> usize {
+                    atomic.load(order) as usize
+                }
+
+                #[inline]
+                fn store(atomic: &Self::Atomic, value: usize, order: Ordering) {
+                    atomic.store(value as $t, order)
+                }
+
+                #[inline]
+                fn wrapping_sub(end: usize, start: usize) -> usize {
+                    (end as $t).wrapping_sub(start as $t) as usize
+                }
+
+                #[inline]
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: usize,
+                    new: usize,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<usize, usize> {
+                    atomic
+                        .compare_exchange(current as $t, new as $t, success, failure)
+                        .map(|value| value as usize)
+                        .map_err(|value| value as usize)
+                }
+            }
+        )*
+    };
+}
+
+impl_index!(u16 => AtomicU16, u32 => AtomicU32, usize => AtomicUsize);
+
+/// Returns the number of elements between two positions of the index type `I`,
+/// which wrap around at the width of the type.
+#[inline]
+pub(crate) fn distance<I: Index>(start: usize, end: usize) -> usize {
+    I::wrapping_sub(end, start)
+}
+
+/// Atomic counter of the index type `I`.
+///
+/// Positions stored in the counter wrap around at the width of the type.
+pub(crate) struct Counter<I: Index>(I::Atomic);
+
+impl<I: Index> Counter<I> {
+    #[inline]
+    pub(crate) fn new(value: usize) -> Self {
+        Self(I::new(value))
+    }
+
+    #[inline]
+    pub(crate) fn load(&self, order: Ordering) -> usize {
+        I::load(&self.0, order)
+    }
+
+    #[inline]
+    pub(crate) fn store(&self, value: usize, order: Ordering) {
+        I::store(&self.0, value, order)
+    }
+
+    /// Adds `value` to the counter with a plain store.
+    ///
+    /// Only the owner of the counter may modify it, so that no read-modify-write
+    /// operation is needed.
+    #[inline]
+    pub(crate) fn add_owned(&self, value: usize, order: Ordering) {
+        let current = self.load(Ordering::Relaxed);
+        self.store(current.wrapping_add(value), order);
+    }
+
+    /// Adds `value` to the counter if it still holds `current`, which may be
+    /// changed by more than one thread.
+    ///
+    /// Returns the value of the counter if it has been changed.
+    #[inline]
+    pub(crate) fn advance_from(&self, current: usize, value: usize) -> Result<(), usize> {
+        I::compare_exchange(
+            &self.0,
+            current,
+            current.wrapping_add(value),
+            Ordering::SeqCst,
+            Ordering::Acquire,
+        )
+        .map(|_| ())
+    }
+
+    /// Subtracts `value` from the counter with a plain store.
+    ///
+    /// Only the owner of the counter may modify it.
+    #[inline]
+    pub(crate) fn sub_owned(&self, value: usize, order: Ordering) {
+        let current = self.load(Ordering::Relaxed);
+        self.store(current.wrapping_sub(value), order);
+    }
+}
@@ -1,21 +1,131 @@
 #![doc = include_str!("../README.md")]
+#![no_std]
 
-use std::{
-    cell::UnsafeCell,
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{
+    alloc::{alloc, alloc_zeroed, dealloc},
+    boxed::Box,
+    vec::Vec,
+};
+use core::{
+    alloc::Layout,
+    fmt,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::ControlFlow,
+    ptr::{self, NonNull},
     slice::{from_raw_parts, from_raw_parts_mut},
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
-    },
 };
 
+use index::{distance, Counter};
+use sync::{Arc, AtomicBool, CachePadded, Ordering, Slots, PROGRESS};
+
+#[cfg(feature = "futures-io")]
+mod async_io;
+#[cfg(feature = "async")]
+mod asynchronous;
+mod batch;
+#[cfg(feature = "std-blocking")]
+mod blocking;
+mod builder;
+mod bytes;
+mod error;
+mod grant;
+mod index;
+#[cfg(all(feature = "double-mapped", target_os = "linux"))]
+mod mapping;
+#[cfg(all(feature = "mio", unix))]
+mod notify;
+mod observer;
+mod overwrite;
+mod region;
+#[cfg(feature = "futures")]
+mod sink;
+#[cfg(feature = "std-blocking")]
+mod strategy;
+#[cfg(feature = "std")]
+mod std_io;
+#[cfg(feature = "futures")]
+mod stream;
+mod sync;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
+#[cfg(feature = "async")]
+mod waker;
+mod zeroable;
+
+#[cfg(feature = "async")]
+pub use asynchronous::{AsyncConsumer, AsyncProducer, PeerDropped, ReadExact, WriteAll};
+pub use batch::WriteBatch;
+#[cfg(feature = "std-blocking")]
+pub use blocking::Waiter;
+pub use builder::RingBufferBuilder;
+pub use error::{Closed, CreateError, ReuniteError, TryReadError};
+pub use grant::{ReadGrant, WriteGrant};
+pub use index::Index;
+#[cfg(all(feature = "mio", unix))]
+pub use notify::Notifier;
+pub use observer::Observer;
+pub use overwrite::OverwriteConsumer;
+pub use region::Region;
+#[cfg(feature = "futures")]
+pub use sink::ProducerSink;
+#[cfg(feature = "std-blocking")]
+pub use strategy::{Backoff, BusySpin, Park, SpinThenYield, WaitStrategy};
+#[cfg(feature = "futures")]
+pub use stream::{ChunkStream, ConsumerStream, FrameStream};
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub use uring::IoVecs;
+pub use zeroable::Zeroable;
+
 /// Producer part of the ring buffer.
-pub struct Producer<T> {
-    buffer: Arc<DirectRingBuffer<T>>,
+///
+/// The producer can be sent to another thread only if the elements can be
+/// sent, since they are received by the consumer on that thread. It can be
+/// shared between threads under the same condition, for example to poll
+/// [`Producer::available`] from a monitoring thread. A shared reference does
+/// not give access to the elements, but it can keep the ring buffer alive
+/// through `Producer::closed` or `Producer::waiter`, so that the elements
+/// may be dropped on the thread sharing the producer.
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (producer, _consumer) = create_ring_buffer::<*const u8>(1);
+/// std::thread::spawn(move || drop(producer));
+/// ```
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// // Can be shared between threads, but not sent to another thread.
+/// struct NotSend(*const u8);
+/// unsafe impl Sync for NotSend {}
+///
+/// let (producer, _consumer) = create_ring_buffer::<NotSend>(1);
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let _ = producer.available();
+///     });
+/// });
+/// ```
+pub struct Producer<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
     index: usize,
+    written: u64,
+    /// Number of elements at the start of the storage known to be initialized.
+    initialized: usize,
+    /// Value of `written` up to which the writable elements hold values that
+    /// were initialized after their slots were last scrubbed. Only used when
+    /// the storage is zeroized.
+    prepared: u64,
+    /// Read position last loaded from the buffer, which may lag behind.
+    cached_read_pos: usize,
 }
 
-impl<T> Producer<T> {
+impl<T, I: Index> Producer<T, I> {
     /// Returns the number of elements available for writing.
     ///
     /// This method returns the number of elements available for writing.
@@ -36,6 +146,226 @@ impl<T> Producer<T> {
         self.buffer.available_write()
     }
 
+    /// Returns an estimate of the number of elements available for writing.
+    ///
+    /// This method behaves like [`Producer::available`], but loads the
+    /// positions with `Relaxed` ordering, so the value may be stale. It is
+    /// intended as a cheap heuristic, for example for sizing the next batch in
+    /// a polling loop; the write methods check the available space again.
+    ///
+    /// # Returns
+    ///
+    /// An estimate of the number of elements available for writing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2]);
+    /// assert_eq!(producer.available_hint(), 3);
+    /// ```
+    pub fn available_hint(&self) -> usize {
+        self.buffer.writable(Ordering::Relaxed)
+    }
+
+    /// Returns the number of elements available for writing before the wrap-around.
+    ///
+    /// This method returns how many elements can be written contiguously, that
+    /// is, the size of the first region the closure of
+    /// [`Producer::write_slices`] receives.
+    ///
+    /// # Returns
+    ///
+    /// Number of elements available for writing up to the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// consumer.advance(3);
+    /// assert_eq!(producer.available(), 5);
+    /// assert_eq!(producer.contiguous_available(), 2);
+    /// ```
+    pub fn contiguous_available(&self) -> usize {
+        self.buffer
+            .contiguous_available(self.index, self.available())
+    }
+
+    /// Returns the capacity of the ring buffer.
+    ///
+    /// This method returns the total number of elements the ring buffer can
+    /// hold, which is the size specified when the buffer was created.
+    ///
+    /// # Returns
+    ///
+    /// Capacity of the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(producer.capacity(), 5);
+    /// producer.write_element(1);
+    /// assert_eq!(producer.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns `true` if the ring buffer contains no elements.
+    ///
+    /// This method returns `true` if there are no elements available for
+    /// reading.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the ring buffer is empty, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(2);
+    /// assert!(producer.is_empty());
+    /// producer.write_element(1);
+    /// assert!(!producer.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns `true` if the ring buffer is full.
+    ///
+    /// This method returns `true` if there is no space available for writing.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the ring buffer is full, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(2);
+    /// assert!(!producer.is_full());
+    /// producer.write_element(1);
+    /// producer.write_element(2);
+    /// assert!(producer.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    /// Returns `true` if the consumer has not been dropped.
+    ///
+    /// Once the consumer has been dropped, nothing reads the ring buffer
+    /// anymore, so a producer waiting for space can stop instead of retrying
+    /// forever. A consumer converted into another handle, such as a stream,
+    /// stays alive until that handle is dropped.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the consumer is alive, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (producer, consumer) = create_ring_buffer::<u8>(2);
+    /// assert!(producer.is_consumer_alive());
+    /// drop(consumer);
+    /// assert!(!producer.is_consumer_alive());
+    /// ```
+    pub fn is_consumer_alive(&self) -> bool {
+        !self.buffer.is_consumer_dropped()
+    }
+
+    /// Returns how full the ring buffer is, as a ratio of its capacity.
+    ///
+    /// This method returns the number of elements available for reading
+    /// divided by the capacity, ranging from `0.0` (empty) to `1.0` (full).
+    /// A buffer with zero capacity always returns `0.0`.
+    ///
+    /// # Returns
+    ///
+    /// The fill ratio of the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(4);
+    /// assert_eq!(producer.fill_ratio(), 0.0);
+    /// producer.write_element(1);
+    /// assert_eq!(producer.fill_ratio(), 0.25);
+    /// ```
+    pub fn fill_ratio(&self) -> f32 {
+        self.buffer.fill_ratio()
+    }
+
+    /// Returns the total number of elements ever written to the ring buffer.
+    ///
+    /// This method returns a monotonic count of the elements written since the
+    /// ring buffer was created, which can be used as a stream position, for
+    /// example a sample clock. Elements retracted with [`Producer::unwrite`]
+    /// are not counted.
+    ///
+    /// # Returns
+    ///
+    /// The total number of elements written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// for value in 0..5 {
+    ///     producer.write_element(value);
+    ///     consumer.read_element();
+    /// }
+    /// assert_eq!(producer.total_written(), 5);
+    /// ```
+    pub fn total_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the total number of elements ever read from the ring buffer.
+    ///
+    /// This method returns a monotonic count of the elements read by the
+    /// consumer since the ring buffer was created. The difference from
+    /// [`Producer::total_written`] is the number of elements available for
+    /// reading.
+    ///
+    /// # Returns
+    ///
+    /// The total number of elements read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// consumer.read_element();
+    /// assert_eq!(producer.total_read(), 1);
+    /// ```
+    pub fn total_read(&self) -> u64 {
+        self.written - self.buffer.available_read() as u64
+    }
+
     /// Writes elements to the ring buffer.
     ///
     /// This method writes elements to the ring buffer using the provided closure.
@@ -48,20 +378,27 @@ impl<T> Producer<T> {
     /// If there is no space available for writing, the function returns immediately
     /// without blocking, and the closure is not called.
     ///
+    /// The slice-based write methods require `T: Copy + Default`. Elements that
+    /// are not `Copy`, such as `String` or `Vec<u8>`, are written one at a time
+    /// with [`Producer::write_element`], or through the `MaybeUninit<T>` slices
+    /// of [`Producer::write_slices_uninit`] and [`Producer::vacant_slices_uninit`].
+    ///
     /// # Arguments
     ///
     /// * `f` - A closure for writing elements. It takes a mutable slice of writable
-    ///         elements and an offset, and returns the number of elements written. The
-    ///         closure will not be called if there are no writable elements. If the
-    ///         buffer wraps around, the closure may be called twice. The slice passed
-    ///         to the closure contains the currently writable elements. The offset is
-    ///         `0` for the first call and increases by the number of elements written
-    ///         in subsequent calls. If the closure returns a value less than the
-    ///         length of the slice passed to it, it is considered as an interruption
-    ///         of the write operation by that number of elements.
+    ///   elements and an offset, and returns the number of elements written. The
+    ///   closure will not be called if there are no writable elements. If the
+    ///   buffer wraps around, the closure may be called twice. The slice passed
+    ///   to the closure contains the currently writable elements. The offset is
+    ///   `0` for the first call and increases by the number of elements written
+    ///   in subsequent calls. If the closure returns a value less than the
+    ///   length of the slice passed to it, it is considered as an interruption
+    ///   of the write operation by that number of elements.
+    ///   The closure must not return a value greater than the length of the
+    ///   slice; otherwise the method panics.
     /// * `max_size` - An optional parameter specifying the maximum number of elements
-    ///                to write. If `None`, the method will write up to the number of
-    ///                available elements.
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
     ///
     /// # Returns
     ///
@@ -85,146 +422,554 @@ impl<T> Producer<T> {
     /// assert_eq!(producer.available(), 0);
     /// ```
     pub fn write_slices(
+        &mut self,
+        f: impl FnMut(&mut [T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        self.write_slices_chunked(f, max_size, usize::MAX)
+    }
+
+    /// Writes elements to the ring buffer in chunks of limited length.
+    ///
+    /// This method behaves like [`Producer::write_slices`], but the slice passed
+    /// to the closure never exceeds `max_chunk` elements. The closure is called
+    /// repeatedly until `max_size` elements (or all available elements) have
+    /// been written, which keeps each closure invocation short.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure for writing elements. See [`Producer::write_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
+    /// * `max_chunk` - The maximum length of the slice passed to the closure.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chunk` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(10);
+    /// let mut calls = 0;
+    /// assert_eq!(producer.write_slices_chunked(|data, offset| {
+    ///     assert_eq!(offset, calls * 4);
+    ///     assert!(data.len() <= 4);
+    ///     calls += 1;
+    ///     data.len()
+    /// }, None, 4), 10);
+    /// assert_eq!(calls, 3);
+    /// ```
+    pub fn write_slices_chunked(
+        &mut self,
+        mut f: impl FnMut(&mut [T], usize) -> usize,
+        max_size: Option<usize>,
+        max_chunk: usize,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        assert!(max_chunk > 0, "max_chunk must be greater than 0");
+        self.write_slices_init_with(
+            |data, offset| ControlFlow::Continue(f(data, offset)),
+            max_size,
+            max_chunk,
+            false,
+        )
+    }
+
+    /// Writes elements to the ring buffer, publishing each chunk immediately.
+    ///
+    /// This method behaves like [`Producer::write_slices_chunked`], but the
+    /// elements written by each closure invocation become available for reading
+    /// as soon as the closure returns, instead of after the whole operation. The
+    /// consumer can therefore start reading while a large write is still in
+    /// progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure for writing elements. See [`Producer::write_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
+    /// * `max_chunk` - The maximum length of the slice passed to the closure.
+    ///   Pass `usize::MAX` to publish only at the wrap-around of the buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chunk` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(10);
+    /// assert_eq!(producer.write_slices_incremental(|data, offset| {
+    ///     // Previously written chunks are already visible to the consumer.
+    ///     assert_eq!(consumer.available(), offset);
+    ///     data.len()
+    /// }, None, 4), 10);
+    /// ```
+    pub fn write_slices_incremental(
         &mut self,
         mut f: impl FnMut(&mut [T], usize) -> usize,
         max_size: Option<usize>,
+        max_chunk: usize,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        assert!(max_chunk > 0, "max_chunk must be greater than 0");
+        self.write_slices_init_with(
+            |data, offset| ControlFlow::Continue(f(data, offset)),
+            max_size,
+            max_chunk,
+            true,
+        )
+    }
+
+    /// Common process of the slice-based write operations on initialized
+    /// elements.
+    fn write_slices_init_with(
+        &mut self,
+        mut f: impl FnMut(&mut [T], usize) -> ControlFlow<usize, usize>,
+        max_size: Option<usize>,
+        max_chunk: usize,
+        incremental: bool,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        let available = self.available_cached(max_size.unwrap_or(usize::MAX));
+        self.initialize_vacant(available);
+        self.write_slices_with(
+            // The writable elements have been initialized.
+            |data, offset| f(unsafe { slice_assume_init_mut(data) }, offset),
+            available,
+            max_size,
+            max_chunk,
+            incremental,
+        )
+    }
+
+    /// Initializes the writable elements that have never been written with
+    /// default values, so that they can be handed out as `&mut [T]`.
+    ///
+    /// Since `T` is `Copy`, an element stays initialized once it is written.
+    fn initialize_vacant(&mut self, available: usize)
+    where
+        T: Copy + Default,
+    {
+        let (first, second) = self.buffer.writable_slices(self.index, available);
+        let (first_start, second_start) = if self.buffer.is_zeroizing() {
+            // Zeroizing the storage leaves the released elements
+            // uninitialized, except those initialized since then.
+            let prepared = self.prepared.saturating_sub(self.written) as usize;
+            let first_start = prepared.min(first.len());
+            (first_start, (prepared - first_start).min(second.len()))
+        } else {
+            (
+                self.initialized.saturating_sub(self.index).min(first.len()),
+                self.initialized.min(second.len()),
+            )
+        };
+        let len = first.len() + second.len();
+        for element in first[first_start..]
+            .iter_mut()
+            .chain(&mut second[second_start..])
+        {
+            element.write(T::default());
+        }
+        self.initialized = self.initialized.max(self.index + first.len());
+        self.prepared = self.prepared.max(self.written + len as u64);
+    }
+
+    /// Forgets that the next `available` writable elements are initialized.
+    ///
+    /// Called before the writable space is handed out as `MaybeUninit<T>`,
+    /// where it may be overwritten with uninitialized values, so that the
+    /// slice-based methods initialize it again.
+    fn uninitialize_vacant(&mut self, available: usize) {
+        self.prepared = self.written;
+        self.initialized = if self.index + available > self.buffer.capacity {
+            // The writable space wraps around to the start of the storage.
+            0
+        } else {
+            self.initialized.min(self.index)
+        };
+    }
+
+    /// Common process of the slice-based write operations.
+    fn write_slices_with(
+        &mut self,
+        mut f: impl FnMut(&mut [MaybeUninit<T>], usize) -> ControlFlow<usize, usize>,
+        available: usize,
+        max_size: Option<usize>,
+        max_chunk: usize,
+        incremental: bool,
     ) -> usize {
-        let available = self.available();
+        let written = &mut self.written;
         self.buffer.process_slices(
             &mut self.index,
             available,
             |buf, len, process_offset| {
                 f(
                     // No boundaries are crossed.
-                    unsafe { from_raw_parts_mut(buf, len) },
+                    unsafe { from_raw_parts_mut(buf.cast(), len) },
                     process_offset,
                 )
             },
             max_size,
-            |atomic, processed| {
-                atomic.fetch_add(processed, Ordering::Release);
+            max_chunk,
+            incremental,
+            |buffer, _, processed| {
+                buffer.publish(processed);
+                *written += processed as u64;
             },
         )
     }
 
-    /// Writes elements to the ring buffer. (Deprecated)
+    /// Writes elements to the ring buffer if enough space is available.
     ///
-    /// This method writes elements to the ring buffer using the provided closure.
+    /// This method behaves like [`Producer::write_slices`], but only if at least
+    /// `min_size` elements are available for writing. Otherwise, it returns `0`
+    /// immediately without calling the closure.
     ///
     /// # Arguments
     ///
-    /// * `f` - A closure for writing elements.
-    /// * `max_size` - An optional parameter specifying the maximum number of elements to write.
+    /// * `min_size` - The minimum number of elements that must be available for
+    ///   writing.
+    /// * `f` - A closure for writing elements. See [`Producer::write_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
     ///
     /// # Returns
     ///
     /// The number of elements written.
-    #[deprecated(note = "Please use `write_slices` instead")]
-    pub fn write(
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(producer.write_slices_at_least(4, |data, _| data.len(), Some(3)), 3);
+    /// assert_eq!(producer.write_slices_at_least(4, |_, _| unreachable!(), None), 0);
+    /// ```
+    pub fn write_slices_at_least(
         &mut self,
+        min_size: usize,
         f: impl FnMut(&mut [T], usize) -> usize,
         max_size: Option<usize>,
-    ) -> usize {
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        if self.available_cached(min_size) < min_size {
+            return 0;
+        }
         self.write_slices(f, max_size)
     }
 
-    /// Writes a single element to the ring buffer.
+    /// Writes elements to the ring buffer until the closure breaks with a result.
     ///
-    /// This method writes a single element to the ring buffer. If the buffer is full,
-    /// it returns `false`.
+    /// This method behaves like [`Producer::write_slices`], but the closure
+    /// returns the number of elements written together with a `ControlFlow`.
+    /// Returning `ControlFlow::Break(value)` stops the operation after the
+    /// written elements have been committed, and `value` is handed back to the
+    /// caller.
     ///
     /// # Arguments
     ///
-    /// * `value` - The element to write to the buffer.
+    /// * `f` - A closure for writing elements. It takes a mutable slice of writable
+    ///   elements and an offset, and returns the number of elements written and
+    ///   whether to continue the operation. See [`Producer::write_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
     ///
     /// # Returns
     ///
-    /// `true` if the element was successfully written, `false` if the buffer is full.
+    /// A tuple of the number of elements written and the value passed to
+    /// `ControlFlow::Break`, or `None` if the closure did not break.
     ///
     /// # Example
     ///
     /// ```
     /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::ops::ControlFlow;
     ///
-    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
-    /// assert!(producer.write_element(1));
-    /// assert!(producer.write_element(2));
-    /// assert!(producer.write_element(3));
-    /// assert!(producer.write_element(4));
-    /// assert!(producer.write_element(5));
-    /// assert!(!producer.write_element(6)); // Buffer is full
-    /// assert_eq!(producer.available(), 0);
-    /// assert_eq!(consumer.available(), 5);
-    /// consumer.read_slices(|data, offset| {
-    ///     assert_eq!(data, &([1, 2, 3, 4, 5][offset..offset + data.len()]));
-    ///     data.len()
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// let source = [1, 2, 3];
+    /// let result = producer.write_slices_until(|data, offset| {
+    ///     let len = data.len().min(source.len() - offset);
+    ///     data[..len].copy_from_slice(&source[offset..offset + len]);
+    ///     (len, ControlFlow::Break("end of source"))
     /// }, None);
-    /// assert_eq!(consumer.available(), 0);
-    /// assert_eq!(producer.available(), 5);
+    /// assert_eq!(result, (3, Some("end of source")));
     /// ```
-    pub fn write_element(&mut self, value: T) -> bool {
-        self.buffer.write_element(&mut self.index, value)
+    pub fn write_slices_until<R>(
+        &mut self,
+        mut f: impl FnMut(&mut [T], usize) -> (usize, ControlFlow<R>),
+        max_size: Option<usize>,
+    ) -> (usize, Option<R>)
+    where
+        T: Copy + Default,
+    {
+        let mut result = None;
+        let written = self.write_slices_init_with(
+            |data, offset| match f(data, offset) {
+                (written, ControlFlow::Continue(())) => ControlFlow::Continue(written),
+                (written, ControlFlow::Break(value)) => {
+                    result = Some(value);
+                    ControlFlow::Break(written)
+                }
+            },
+            max_size,
+            usize::MAX,
+            false,
+        );
+        (written, result)
     }
-}
 
-unsafe impl<T> Send for Producer<T> {}
+    /// Writes elements to the ring buffer using a fallible closure.
+    ///
+    /// This method behaves like [`Producer::write_slices`], but the closure
+    /// returns a `Result`. If the closure returns an error, the operation stops,
+    /// the elements written by the previous invocations are committed, and the
+    /// error is returned together with the number of those elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure for writing elements. It takes a mutable slice of writable
+    ///   elements and an offset, and returns the number of elements written or
+    ///   an error. See [`Producer::write_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the number of elements written, or `Err` containing the
+    /// number of elements written before the error and the error itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::io::Read;
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(8);
+    /// let mut reader: &[u8] = &[1, 2, 3];
+    /// assert_eq!(producer.write_slices_result(|data, _| reader.read(data), None).unwrap(), 3);
+    /// assert_eq!(consumer.available(), 3);
+    /// ```
+    pub fn write_slices_result<E>(
+        &mut self,
+        mut f: impl FnMut(&mut [T], usize) -> Result<usize, E>,
+        max_size: Option<usize>,
+    ) -> Result<usize, (usize, E)>
+    where
+        T: Copy + Default,
+    {
+        let mut error = None;
+        let written = self.write_slices_init_with(
+            |data, offset| match f(data, offset) {
+                Ok(written) => ControlFlow::Continue(written),
+                Err(e) => {
+                    error = Some(e);
+                    ControlFlow::Break(0)
+                }
+            },
+            max_size,
+            usize::MAX,
+            false,
+        );
+        match error {
+            None => Ok(written),
+            Some(e) => Err((written, e)),
+        }
+    }
 
-/// Consumer part of the ring buffer.
-pub struct Consumer<T> {
-    buffer: Arc<DirectRingBuffer<T>>,
-    index: usize,
-}
+    /// Writes elements to the ring buffer. (Deprecated)
+    ///
+    /// This method writes elements to the ring buffer using the provided closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure for writing elements.
+    /// * `max_size` - An optional parameter specifying the maximum number of elements to write.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    #[deprecated(note = "Please use `write_slices` instead")]
+    pub fn write(
+        &mut self,
+        f: impl FnMut(&mut [T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize
+    where
+        T: Copy + Default,
+    {
+        self.write_slices(f, max_size)
+    }
 
-impl<T> Consumer<T> {
-    /// Returns the number of elements available for reading.
+    /// Writes elements from a slice to the ring buffer.
     ///
-    /// This method returns the number of elements available for reading.
+    /// This method copies up to `src.len()` elements from `src` into the ring
+    /// buffer, handling the wrap-around of the buffer internally. If less space
+    /// is available, only as many elements as fit are copied. The elements are
+    /// copied with at most two `memcpy` calls, without going through a closure.
+    ///
+    /// In overwrite mode, the oldest unread elements are evicted to make room
+    /// instead, and only the last elements of `src` are written if it is longer
+    /// than the capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The elements to write to the buffer.
     ///
     /// # Returns
     ///
-    /// Number of elements available for reading.
+    /// The number of elements written.
     ///
     /// # Example
+    ///
     /// ```
     /// use direct_ring_buffer::{create_ring_buffer};
     ///
-    /// let (_, consumer) = create_ring_buffer::<u8>(5);
-    /// assert_eq!(consumer.available(), 0);
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(producer.write_from_slice(&[1, 2, 3]), 3);
+    /// assert_eq!(producer.write_from_slice(&[4, 5, 6]), 2);
+    /// assert_eq!(consumer.read_array::<5>(), Some([1, 2, 3, 4, 5]));
     /// ```
-    pub fn available(&self) -> usize {
-        self.buffer.available_read()
+    pub fn write_from_slice(&mut self, src: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let src = if self.buffer.overwrite {
+            let src = &src[src.len().saturating_sub(self.capacity())..];
+            self.evict_for(src.len());
+            src
+        } else {
+            src
+        };
+        let len = src.len().min(self.available_cached(src.len()));
+        if len == 0 {
+            return 0;
+        }
+        // The space is writable and `T` is `Copy`.
+        unsafe { self.buffer.copy_from_slice(self.index, &src[..len]) };
+        self.buffer.wraparound_index(&mut self.index, len);
+        self.buffer.publish(len);
+        self.written += len as u64;
+        len
     }
 
-    /// Reads elements from the ring buffer.
+    /// Writes all elements of a slice to the ring buffer, or none at all.
     ///
-    /// This method reads elements from the ring buffer using the provided closure.
-    /// The closure `f` receives a slice of readable elements and the current
-    /// offset within the read operation, and it should return the number of elements
-    /// read. The `max_size` parameter specifies the maximum number of elements to
-    /// read. If `None`, the method attempts to read as many elements as available.
+    /// This method writes the entire slice only if there is enough space
+    /// available for all of its elements. Otherwise, nothing is written and the
+    /// buffer is left untouched, so the consumer never observes a partially
+    /// written slice.
     ///
-    /// If there are no elements available for reading, the function returns
-    /// immediately without blocking, and the closure is not called.
+    /// # Arguments
+    ///
+    /// * `src` - The elements to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if all elements were written, `false` if there was not enough
+    /// space available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// assert!(producer.write_exact(&[1, 2, 3]));
+    /// assert!(!producer.write_exact(&[4, 5, 6])); // Not enough space
+    /// assert_eq!(producer.available(), 2);
+    /// assert!(producer.write_exact(&[4, 5]));
+    /// assert_eq!(producer.available(), 0);
+    /// ```
+    pub fn write_exact(&mut self, src: &[T]) -> bool
+    where
+        T: Copy,
+    {
+        self.evict_for(src.len());
+        if self.available_cached(src.len()) < src.len() {
+            return false;
+        }
+        self.write_from_slice(src);
+        true
+    }
+
+    /// Writes all `N` elements of an array to the ring buffer, or none at all.
+    ///
+    /// This method writes the array only if there is space available for all of
+    /// its elements. Otherwise, nothing is written and the array is returned back
+    /// to the caller.
     ///
     /// # Arguments
     ///
-    /// * `f` - A closure that processes the readable elements. It takes a reference
-    ///         to a slice of readable elements and an offset as arguments, and
-    ///         returns the number of elements read. The closure will not be called if
-    ///         there are no readable elements. If the buffer wraps around, the closure
-    ///         may be called twice. The slice passed to the closure contains the
-    ///         currently accessible elements. The offset is `0` for the first call
-    ///         and increases by the number of elements read in subsequent calls. If
-    ///         the closure returns a value less than the length of the slice passed to
-    ///         it, it is considered as an interruption of the read operation by that
-    ///         number of elements.
-    /// * `max_size` - An optional parameter specifying the maximum number of elements
-    ///                to read. If `None`, the method will read up to the number of
-    ///                available elements.
+    /// * `array` - The elements to write to the buffer.
     ///
     /// # Returns
     ///
-    /// The number of elements read.
+    /// `Ok(())` if all elements were written, or `Err` containing the array if
+    /// there was not enough space available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(producer.write_array([1, 2, 3]), Ok(()));
+    /// assert_eq!(producer.write_array([4, 5, 6]), Err([4, 5, 6])); // Not enough space
+    /// assert_eq!(producer.available(), 2);
+    /// ```
+    pub fn write_array<const N: usize>(&mut self, array: [T; N]) -> Result<(), [T; N]>
+    where
+        T: Copy,
+    {
+        if self.write_exact(&array) {
+            Ok(())
+        } else {
+            Err(array)
+        }
+    }
+
+    /// Returns the writable space of the ring buffer as two mutable slices.
+    ///
+    /// The first slice contains the writable elements from the current write
+    /// position up to the end of the buffer, and the second slice contains the
+    /// writable elements that wrap around to the beginning of the buffer. The
+    /// second slice is empty if the writable space does not wrap around. The
+    /// written elements become readable only after calling [`Producer::commit`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple of two mutable slices containing the writable elements in order.
     ///
     /// # Example
     ///
@@ -232,198 +977,3188 @@ impl<T> Consumer<T> {
     /// use direct_ring_buffer::{create_ring_buffer};
     ///
     /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
-    /// producer.write_slices(|data, offset| {
-    ///     assert_eq!(data.len(), 5);
-    ///     data[..2].copy_from_slice(&[1, 2]);
-    ///     2
-    /// }, None);
-    /// consumer.read_slices(|data, offset| {
-    ///     assert_eq!(data.len(), 2);
-    ///     assert_eq!(offset, 0);
-    ///     2
-    /// }, None);
-    /// producer.write_slices(|data, offset| {
-    ///     data.copy_from_slice(&([3, 4, 5, 6, 7][offset..offset + data.len()]));
-    ///     data.len()
-    /// }, None);
-    /// consumer.read_slices(|data, offset| {
-    ///     assert_eq!(data, &([3, 4, 5, 6, 7][offset..offset + data.len()]));
-    ///     data.len()
-    /// }, None);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// consumer.advance(2);
+    /// let (first, second) = producer.vacant_slices_mut();
+    /// assert_eq!((first.len(), second.len()), (2, 2));
+    /// first.copy_from_slice(&[4, 5]);
+    /// second[0] = 6;
+    /// assert_eq!(producer.commit(3), 3);
+    /// assert_eq!(consumer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    /// ```
+    pub fn vacant_slices_mut(&mut self) -> (&mut [T], &mut [T])
+    where
+        T: Copy + Default,
+    {
+        let available = self.available_cached(usize::MAX);
+        self.initialize_vacant(available);
+        let (first, second) = self.buffer.writable_slices(self.index, available);
+        // The writable elements have been initialized.
+        unsafe { (slice_assume_init_mut(first), slice_assume_init_mut(second)) }
+    }
+
+    /// Returns the writable space of the ring buffer as two uninitialized
+    /// slices.
+    ///
+    /// This method behaves like [`Producer::vacant_slices_mut`], but it does not
+    /// require the elements to be `Copy` or `Default`, and it does not
+    /// initialize the writable space beforehand. Every element must be
+    /// initialized before it is published with [`Producer::advance`]. The
+    /// elements that are left unpublished are initialized again by the
+    /// methods that hand out the writable space as `&mut [T]`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of two mutable slices containing the writable elements in order.
+    ///
+    /// # Example
     ///
     /// ```
-    pub fn read_slices(
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<String>(3);
+    /// let (first, _) = producer.vacant_slices_uninit();
+    /// first[0].write("hello".to_string());
+    /// unsafe { producer.advance(1) };
+    /// assert_eq!(consumer.read_element().as_deref(), Some("hello"));
+    /// ```
+    pub fn vacant_slices_uninit(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let available = self.available_cached(usize::MAX);
+        self.uninitialize_vacant(available);
+        self.buffer.writable_slices(self.index, available)
+    }
+
+    /// Writes elements to the ring buffer through uninitialized slices.
+    ///
+    /// This method behaves like [`Producer::write_slices`], but the closure
+    /// receives the writable space as `MaybeUninit<T>`, so that it can be used
+    /// with elements that are not `Copy` or `Default`, and without initializing
+    /// the space before it is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure for writing elements. It receives an uninitialized
+    ///   slice and the offset from the start of the operation, and returns the
+    ///   number of elements written.
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to write. If `None`, the method will write up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    ///
+    /// # Safety
+    ///
+    /// The closure must initialize the first `n` elements of the slice, where
+    /// `n` is the value it returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<String>(3);
+    /// let written = unsafe {
+    ///     producer.write_slices_uninit(|data, offset| {
+    ///         for (i, element) in data.iter_mut().enumerate() {
+    ///             element.write((offset + i).to_string());
+    ///         }
+    ///         data.len()
+    ///     }, Some(2))
+    /// };
+    /// assert_eq!(written, 2);
+    /// assert_eq!(consumer.read_element().as_deref(), Some("0"));
+    /// assert_eq!(consumer.read_element().as_deref(), Some("1"));
+    /// ```
+    pub unsafe fn write_slices_uninit(
         &mut self,
-        mut f: impl FnMut(&[T], usize) -> usize,
+        mut f: impl FnMut(&mut [MaybeUninit<T>], usize) -> usize,
         max_size: Option<usize>,
     ) -> usize {
-        let available = self.available();
-        self.buffer.process_slices(
-            &mut self.index,
+        let available = self.available_cached(max_size.unwrap_or(usize::MAX));
+        self.uninitialize_vacant(available);
+        self.write_slices_with(
+            |data, offset| ControlFlow::Continue(f(data, offset)),
             available,
-            |buf, len, process_offset| {
-                f(
-                    // No boundaries are crossed.
-                    unsafe { from_raw_parts(buf, len) },
-                    process_offset,
-                )
+            max_size,
+            usize::MAX,
+            false,
+        )
+    }
+
+    /// Returns the base pointer of the ring buffer storage.
+    ///
+    /// This method, together with [`Producer::writable_regions`], allows code
+    /// outside of Rust, such as C audio drivers or DMA controllers, to write
+    /// directly into the storage. The written elements become readable only
+    /// after calling [`Producer::advance`].
+    ///
+    /// # Returns
+    ///
+    /// A pointer to the first element of the storage. The storage holds
+    /// [`Producer::capacity`] elements and is never reallocated while the
+    /// producer or the consumer is alive.
+    ///
+    /// Writing through the pointer is allowed only within the regions returned
+    /// by [`Producer::writable_regions`], and only until they are passed to
+    /// [`Producer::advance`].
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.buffer.as_mut_ptr()
+    }
+
+    /// Returns the writable space of the ring buffer as two regions.
+    ///
+    /// This method is the raw-parts counterpart of
+    /// [`Producer::vacant_slices_mut`]. The first region starts at the current
+    /// write position, and the second region starts at the beginning of the
+    /// storage and is empty if the writable space does not wrap around.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of two regions relative to [`Producer::as_mut_ptr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer, Region};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// consumer.advance(3);
+    /// let (first, second) = producer.writable_regions();
+    /// assert_eq!(first, Region { offset: 4, len: 1 });
+    /// assert_eq!(second, Region { offset: 0, len: 3 });
+    ///
+    /// // Stands in for a C driver writing into the storage.
+    /// let base = producer.as_mut_ptr();
+    /// unsafe {
+    ///     base.add(first.offset).write(5);
+    ///     base.add(second.offset).write(6);
+    ///     assert_eq!(producer.advance(2), 2);
+    /// }
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![4, 5, 6]);
+    /// ```
+    pub fn writable_regions(&self) -> (Region, Region) {
+        self.buffer.regions(self.index, self.available())
+    }
+
+    /// Makes written elements available for reading.
+    ///
+    /// This method marks up to `n` elements, starting at the current write
+    /// position, as written. It is typically used after filling the slices
+    /// returned by [`Producer::vacant_slices_mut`].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of elements to commit.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements committed, which is `n` or the number of
+    /// writable elements, whichever is smaller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(5);
+    /// producer.vacant_slices_mut().0[..2].copy_from_slice(&[1, 2]);
+    /// assert_eq!(producer.commit(2), 2);
+    /// assert_eq!(consumer.available(), 2);
+    /// assert_eq!(producer.commit(10), 3);
+    /// ```
+    pub fn commit(&mut self, n: usize) -> usize
+    where
+        T: Copy + Default,
+    {
+        let n = n.min(self.available_cached(n));
+        // Publishes the written contents as they are, initializing only the
+        // elements that have not been handed out as `&mut [T]` yet.
+        self.initialize_vacant(n);
+        unsafe { self.advance(n) }
+    }
+
+    /// Makes elements written outside of Rust available for reading.
+    ///
+    /// This method marks up to `n` elements, starting at the current write
+    /// position, as written, without accessing them. It is intended for data
+    /// placed directly into the backing memory by a DMA controller or an FFI
+    /// callee, through a pointer obtained from [`Producer::vacant_slices_mut`].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of elements to mark as written.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements marked as written, which is `n` or the number of
+    /// writable elements, whichever is smaller.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that:
+    ///
+    /// - The first `n` writable elements (or all of them, if fewer) hold valid
+    ///   values of `T`.
+    /// - The external writes to these elements have completed and are visible
+    ///   to the current thread, for example by waiting for the DMA transfer to
+    ///   finish and issuing any fence the platform requires.
+    /// - Nothing writes to these elements after this call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// let dst = producer.vacant_slices_mut().0.as_mut_ptr();
+    /// // Stands in for a DMA transfer or an FFI call.
+    /// unsafe { std::ptr::copy_nonoverlapping([1, 2, 3].as_ptr(), dst, 3) };
+    /// assert_eq!(unsafe { producer.advance(3) }, 3);
+    /// assert_eq!(consumer.read_array::<3>(), Some([1, 2, 3]));
+    /// ```
+    pub unsafe fn advance(&mut self, n: usize) -> usize {
+        let available = self.available_cached(n);
+        self.write_slices_with(
+            |data, _| ControlFlow::Continue(data.len()),
+            available,
+            Some(n),
+            usize::MAX,
+            false,
+        )
+    }
+
+    /// Retracts the most recently written elements that have not been read yet.
+    ///
+    /// This method removes up to `n` of the newest elements from the ring buffer,
+    /// as if they had never been written, and moves the write position back
+    /// accordingly. Elements already read by the consumer cannot be retracted.
+    /// Because the consumer could otherwise be reading the retracted elements
+    /// concurrently, this method requires exclusive access to the consumer of the
+    /// same ring buffer. Nothing is retracted while an [`Observer`] exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    /// * `n` - The maximum number of elements to retract.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements retracted, which is `n` or the number of readable
+    /// elements, whichever is smaller, or `0` while an observer exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` does not belong to the same ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// assert_eq!(producer.unwrite(&mut consumer, 2), 2);
+    /// producer.write_exact(&[5]);
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![1, 2, 5]);
+    /// ```
+    pub fn unwrite(&mut self, consumer: &mut Consumer<T, I>, n: usize) -> usize {
+        assert!(
+            Arc::ptr_eq(&self.buffer, &consumer.buffer),
+            "consumer belongs to a different ring buffer"
+        );
+        let retracted = self.buffer.unwrite(&mut self.index, n);
+        self.written -= retracted as u64;
+        // The retracted elements have been scrubbed.
+        self.prepared = self.written;
+        // The write position cached by the consumer may have been retracted.
+        consumer.invalidate_cache();
+        retracted
+    }
+
+    /// Resets the ring buffer to its initial empty state for reuse.
+    ///
+    /// This method discards all unread elements and resets the write and read
+    /// positions and the counts returned by [`Producer::total_written`] and
+    /// [`Consumer::total_read`], so that the same allocation can be used for a
+    /// new stream. Because the consumer could otherwise be accessing the buffer
+    /// concurrently, this method requires exclusive access to the consumer of
+    /// the same ring buffer. Nothing is reset while an [`Observer`] exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the ring buffer was reset, `false` if an observer exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` does not belong to the same ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// consumer.advance(1);
+    /// assert!(producer.reset(&mut consumer));
+    /// assert_eq!(producer.available(), 5);
+    /// assert_eq!(consumer.available(), 0);
+    /// assert_eq!((producer.total_written(), consumer.total_read()), (0, 0));
+    /// ```
+    pub fn reset(&mut self, consumer: &mut Consumer<T, I>) -> bool {
+        assert!(
+            Arc::ptr_eq(&self.buffer, &consumer.buffer),
+            "consumer belongs to a different ring buffer"
+        );
+        if !self.buffer.reset(consumer.index) {
+            return false;
+        }
+        self.index = 0;
+        self.written = 0;
+        self.prepared = 0;
+        consumer.index = 0;
+        consumer.read = 0;
+        consumer.invalidate_cache();
+        true
+    }
+
+    /// Makes room for new elements by evicting the oldest unread ones.
+    ///
+    /// This method discards as many of the oldest unread elements as needed so
+    /// that at least `n` elements can be written, which turns the following
+    /// write into one that overwrites the oldest data instead of being
+    /// rejected. This suits telemetry or recordings of the last seconds of
+    /// audio, where the newest elements matter more than the oldest. Because
+    /// the consumer could otherwise be reading the evicted elements
+    /// concurrently, this method requires exclusive access to the consumer of
+    /// the same ring buffer. If the consumer runs on another thread, a ring
+    /// buffer created by [`RingBufferBuilder::build_overwriting`] evicts the
+    /// oldest elements on its own. The evicted elements are dropped and counted by
+    /// [`Consumer::total_read`]. Elements whose space is still held by an
+    /// attached [`Observer`] are not evicted, since that would not free any
+    /// space.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    /// * `n` - The number of elements that must be writable. It is limited to
+    ///   the capacity.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements evicted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` does not belong to the same ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(producer.make_room(&mut consumer, 2), 1);
+    /// assert!(producer.write_exact(&[4, 5]));
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    /// ```
+    pub fn make_room(&mut self, consumer: &mut Consumer<T, I>, n: usize) -> usize {
+        assert!(
+            Arc::ptr_eq(&self.buffer, &consumer.buffer),
+            "consumer belongs to a different ring buffer"
+        );
+        let missing = n.min(self.capacity()).saturating_sub(self.available());
+        consumer.advance(missing.min(self.buffer.evictable()))
+    }
+
+    /// Writes a single element, evicting the oldest unread element if the
+    /// buffer is full.
+    ///
+    /// This method behaves like [`Producer::try_write_element`], but a full
+    /// buffer makes room through [`Consumer::read_element`] instead of
+    /// rejecting the element. Like [`Producer::make_room`], it requires
+    /// exclusive access to the consumer of the same ring buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    /// * `value` - The element to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the evicted element, or `None` if the buffer was not
+    /// full, or `Err` containing the element if an [`Observer`] still holds the
+    /// space. Nothing is evicted in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` does not belong to the same ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// assert_eq!(producer.force_write_element(&mut consumer, 1), Ok(None));
+    /// assert_eq!(producer.force_write_element(&mut consumer, 2), Ok(None));
+    /// assert_eq!(producer.force_write_element(&mut consumer, 3), Ok(Some(1)));
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn force_write_element(
+        &mut self,
+        consumer: &mut Consumer<T, I>,
+        value: T,
+    ) -> Result<Option<T>, T> {
+        assert!(
+            Arc::ptr_eq(&self.buffer, &consumer.buffer),
+            "consumer belongs to a different ring buffer"
+        );
+        let evicted = if self.available() == 0 {
+            if self.buffer.evictable() == 0 {
+                return Err(value);
+            }
+            consumer.read_element()
+        } else {
+            None
+        };
+        self.try_write_element(value).map(|()| evicted)
+    }
+
+    /// Writes the elements of a slice, evicting the oldest unread elements to
+    /// make room for them.
+    ///
+    /// This method behaves like [`Producer::write_exact`], but makes room
+    /// through [`Producer::make_room`] instead of rejecting the slice. If the
+    /// slice is longer than the capacity, only its last elements, as many as
+    /// the capacity, are written. The newest elements are thus always kept,
+    /// which suits flight recorders and similar loggers. Like
+    /// [`Producer::make_room`], it requires exclusive access to the consumer
+    /// of the same ring buffer. While an [`Observer`] holds some of the space,
+    /// only the last elements that fit are written, and the elements before
+    /// them are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    /// * `src` - The elements to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements dropped, which are the unread elements evicted
+    /// and the elements at the start of `src` that were not written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` does not belong to the same ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// assert_eq!(producer.write_from_slice_overwrite(&mut consumer, &[1, 2, 3]), 0);
+    /// assert_eq!(producer.write_from_slice_overwrite(&mut consumer, &[4, 5, 6]), 2);
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    /// // The first element does not fit into the buffer at all.
+    /// assert_eq!(producer.write_from_slice_overwrite(&mut consumer, &[7, 8, 9, 10, 11]), 1);
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![8, 9, 10, 11]);
+    /// ```
+    pub fn write_from_slice_overwrite(&mut self, consumer: &mut Consumer<T, I>, src: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let evicted = self.make_room(consumer, src.len());
+        let skipped = src.len().saturating_sub(self.available());
+        self.write_from_slice(&src[skipped..]);
+        evicted + skipped
+    }
+
+    /// Recovers the storage of the ring buffer from the producer and the
+    /// consumer.
+    ///
+    /// This method consumes both handles and returns the storage as a `Vec`
+    /// containing the unread elements in order. The capacity of the `Vec` is at
+    /// least the capacity of the ring buffer, so the allocation can be reused,
+    /// for example with [`create_ring_buffer_from_with_capacity`].
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The consumer of the same ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the unread elements, or a [`ReuniteError`] giving
+    /// back both handles if `consumer` belongs to a different ring buffer or an
+    /// [`Observer`] of the ring buffer still exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer, create_ring_buffer_from_with_capacity};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// consumer.advance(3);
+    /// producer.write_exact(&[5, 6]);
+    /// let unread = producer.reunite(consumer).unwrap();
+    /// assert_eq!(unread, vec![4, 5, 6]);
+    ///
+    /// let capacity = unread.capacity();
+    /// let (producer, _consumer) = create_ring_buffer_from_with_capacity(unread, capacity);
+    /// assert_eq!(producer.available(), capacity - 3);
+    /// ```
+    pub fn reunite(self, consumer: Consumer<T, I>) -> Result<Vec<T>, ReuniteError<T, I>> {
+        if !Arc::ptr_eq(&self.buffer, &consumer.buffer) || Arc::strong_count(&self.buffer) != 2 {
+            return Err(ReuniteError(self, consumer));
+        }
+        let index = consumer.index;
+        let buffer = Arc::clone(&self.buffer);
+        drop((self, consumer));
+        let mut buffer = match Arc::try_unwrap(buffer) {
+            Ok(buffer) => buffer,
+            Err(_) => unreachable!("the ring buffer is still shared"),
+        };
+        // Takes the elements out, leaving nothing for the buffer to drop.
+        let used = buffer.available_read();
+        if buffer.is_over_aligned() || buffer.is_double_mapped() {
+            // The storage cannot become a `Vec`, so the elements are moved to a
+            // new one.
+            let mut vec = Vec::with_capacity(buffer.capacity());
+            let (first, second) = buffer.slices(index, used);
+            unsafe {
+                let dst = vec.as_mut_ptr();
+                ptr::copy_nonoverlapping(first.as_ptr(), dst, first.len());
+                ptr::copy_nonoverlapping(second.as_ptr(), dst.add(first.len()), second.len());
+                vec.set_len(used);
+                // Only the moved-out bytes remain in the storage.
+                buffer.wipe(buffer.as_mut_ptr(), buffer.capacity());
+            }
+            buffer.free_elements();
+            return Ok(vec);
+        }
+        let mut vec = buffer.take_elements().into_vec();
+        vec.rotate_left(index);
+        let mut vec = ManuallyDrop::new(vec);
+        // The unread elements are initialized, and `MaybeUninit<T>` has the
+        // same layout as `T`.
+        Ok(unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast(), used, vec.capacity()) })
+    }
+
+    /// Creates an observer of the elements written to the ring buffer.
+    ///
+    /// The returned [`Observer`] reads the elements written after this call with
+    /// its own cursor, independently of the consumer. It never frees space in the
+    /// ring buffer; that is still controlled by the consumer. However, the
+    /// producer does not overwrite elements the observer has not read yet, so an
+    /// observer that stops reading eventually blocks the producer. Only one
+    /// observer can exist at a time.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the observer, or `None` if an observer already
+    /// exists, the storage is zeroized, since the consumer then wipes the
+    /// elements the observer may be reading, or the ring buffer is in
+    /// overwrite mode, since the producer then overwrites them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// let mut observer = producer.observer().unwrap();
+    /// assert!(producer.observer().is_none());
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(observer.read_element(), Some(1));
+    /// assert_eq!(consumer.read_element(), Some(1));
+    /// assert_eq!(observer.read_element(), Some(2));
+    /// ```
+    pub fn observer(&mut self) -> Option<Observer<T, I>>
+    where
+        T: Copy,
+    {
+        if self.buffer.is_zeroizing() || self.buffer.overwrite {
+            return None;
+        }
+        Observer::attach(&self.buffer, self.index)
+    }
+
+    /// Grants access to a contiguous writable region of the ring buffer.
+    ///
+    /// This method reserves up to `max` writable elements from the current write
+    /// position up to the end of the buffer, and returns them as a
+    /// [`WriteGrant`]. Unlike the closure-based methods, the grant can be passed
+    /// to external code across multiple statements. The written elements become
+    /// readable only after calling [`WriteGrant::commit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of elements to grant.
+    ///
+    /// # Returns
+    ///
+    /// A `WriteGrant` over the writable region, or `None` if no elements can be
+    /// written or `max` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::io::Read;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// let mut reader: &[u8] = &[1, 2, 3];
+    /// let mut grant = producer.grant(4).unwrap();
+    /// assert_eq!(grant.len(), 4);
+    /// let len = reader.read(&mut grant).unwrap();
+    /// grant.commit(len);
+    /// assert_eq!(consumer.read_array::<3>(), Some([1, 2, 3]));
+    /// ```
+    pub fn grant(&mut self, max: usize) -> Option<WriteGrant<'_, T, I>>
+    where
+        T: Copy + Default,
+    {
+        WriteGrant::new(self, max)
+    }
+
+    /// Starts a batch of single-element writes that are published together.
+    ///
+    /// The elements written through the returned [`WriteBatch`] become
+    /// readable when it is flushed or dropped. Compared with calling
+    /// [`Producer::write_element`] repeatedly, the write position shared with
+    /// the consumer is updated once per batch instead of once per element.
+    ///
+    /// # Returns
+    ///
+    /// A `WriteBatch` borrowing the producer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<f32>(64);
+    /// {
+    ///     let mut batch = producer.batch();
+    ///     for i in 0..48 {
+    ///         batch.write_element(i as f32 * 0.5);
+    ///     }
+    /// }
+    /// assert_eq!(consumer.available(), 48);
+    /// assert_eq!(consumer.read_element(), Some(0.0));
+    /// ```
+    pub fn batch(&mut self) -> WriteBatch<'_, T, I> {
+        WriteBatch::new(self)
+    }
+
+    /// Writes a single element to the ring buffer.
+    ///
+    /// This method writes a single element to the ring buffer. If the buffer is full,
+    /// it returns `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The element to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the element was successfully written, `false` if the buffer is full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// assert!(producer.write_element(1));
+    /// assert!(producer.write_element(2));
+    /// assert!(producer.write_element(3));
+    /// assert!(producer.write_element(4));
+    /// assert!(producer.write_element(5));
+    /// assert!(!producer.write_element(6)); // Buffer is full
+    /// assert_eq!(producer.available(), 0);
+    /// assert_eq!(consumer.available(), 5);
+    /// consumer.read_slices(|data, offset| {
+    ///     assert_eq!(data, &([1, 2, 3, 4, 5][offset..offset + data.len()]));
+    ///     data.len()
+    /// }, None);
+    /// assert_eq!(consumer.available(), 0);
+    /// assert_eq!(producer.available(), 5);
+    /// ```
+    pub fn write_element(&mut self, value: T) -> bool {
+        self.write_element_with(|| value)
+    }
+
+    /// Writes a single element to the ring buffer, returning it back on failure.
+    ///
+    /// This method behaves like [`Producer::write_element`], but if the buffer
+    /// is full, the element is returned back to the caller instead of being
+    /// dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The element to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the element was successfully written, or `Err` containing the
+    /// element if the buffer is full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _) = create_ring_buffer::<u8>(1);
+    /// assert_eq!(producer.try_write_element(1), Ok(()));
+    /// assert_eq!(producer.try_write_element(2), Err(2)); // Buffer is full
+    /// ```
+    pub fn try_write_element(&mut self, value: T) -> Result<(), T> {
+        self.evict_for(1);
+        if self.is_full() {
+            Err(value)
+        } else {
+            // Only this producer can reduce the space, so the write succeeds.
+            self.write_element(value);
+            Ok(())
+        }
+    }
+
+    /// Writes a clone of a single element to the ring buffer.
+    ///
+    /// This method writes a clone of `value` to the ring buffer. The value is
+    /// cloned only if there is space available, so no clone is made when the
+    /// buffer is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A reference to the element to write to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the element was successfully written, `false` if the buffer is full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(1);
+    /// let value = 1;
+    /// assert!(producer.write_element_ref(&value));
+    /// assert!(!producer.write_element_ref(&value)); // Buffer is full
+    /// assert_eq!(consumer.read_element(), Some(1));
+    /// ```
+    pub fn write_element_ref(&mut self, value: &T) -> bool
+    where
+        T: Clone,
+    {
+        self.write_element_with(|| value.clone())
+    }
+
+    /// Fills all writable space of the ring buffer with clones of a value.
+    ///
+    /// This method is useful for priming the buffer at start-up or masking
+    /// underruns, for example with silence samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to fill the buffer with.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<i16>(4);
+    /// producer.write_element(1);
+    /// assert_eq!(producer.fill_with(0), 3);
+    /// assert!(producer.is_full());
+    /// assert_eq!(consumer.read_array::<4>(), Some([1, 0, 0, 0]));
+    /// ```
+    pub fn fill_with(&mut self, value: T) -> usize
+    where
+        T: Clone,
+    {
+        self.fill_with_fn(|| value.clone())
+    }
+
+    /// Fills all writable space of the ring buffer with values produced by a
+    /// closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure called once for each writable element, in order.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// let mut next = 0;
+    /// assert_eq!(producer.fill_with_fn(|| { next += 1; next }), 4);
+    /// assert_eq!(consumer.read_array::<4>(), Some([1, 2, 3, 4]));
+    /// ```
+    pub fn fill_with_fn(&mut self, mut f: impl FnMut() -> T) -> usize {
+        let available = self.available_cached(usize::MAX);
+        self.write_slices_with(
+            |data, _| {
+                for element in data.iter_mut() {
+                    element.write(f());
+                }
+                ControlFlow::Continue(data.len())
+            },
+            available,
+            None,
+            usize::MAX,
+            false,
+        )
+    }
+
+    /// Common process of the single-element write operations.
+    fn write_element_with(&mut self, f: impl FnOnce() -> T) -> bool {
+        self.evict_for(1);
+        if self.available_cached(1) == 0 {
+            return false;
+        }
+        self.buffer.write_element(&mut self.index, f);
+        self.buffer.publish(1);
+        self.written += 1;
+        true
+    }
+
+    /// Returns the number of elements available for writing, loading the read
+    /// position only if fewer than `needed` elements look writable.
+    #[inline]
+    fn available_cached(&mut self, needed: usize) -> usize {
+        self.buffer
+            .available_write_cached(&mut self.cached_read_pos, needed)
+    }
+
+    /// Evicts the oldest unread elements in overwrite mode, so that `needed`
+    /// elements can be written. Nothing is evicted if they cannot fit at all.
+    #[inline]
+    fn evict_for(&mut self, needed: usize) {
+        if self.buffer.overwrite
+            && needed <= self.capacity()
+            && self.available_cached(needed) < needed
+        {
+            self.buffer.evict(&mut self.cached_read_pos, needed);
+        }
+    }
+}
+
+unsafe impl<T: Send, I: Index> Send for Producer<T, I> {}
+// Shared references only give access to the positions, not to the elements,
+// but they can clone the ring buffer, which drops the elements on that thread.
+unsafe impl<T: Send, I: Index> Sync for Producer<T, I> {}
+
+impl<T, I: Index> Drop for Producer<T, I> {
+    fn drop(&mut self) {
+        // Wakes the consumer waiting for elements that will never come.
+        self.buffer.producer_dropped.store(true, PROGRESS);
+        self.buffer.wake_consumer();
+        #[cfg(feature = "async")]
+        self.buffer.producer_drop_waker.wake();
+    }
+}
+
+impl<T, I: Index> fmt::Debug for Producer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("capacity", &self.capacity())
+            .field("index", &self.index)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Consumer part of the ring buffer.
+///
+/// The consumer can be sent to another thread only if the elements can be
+/// sent, since they are written by the producer on another thread. It can be
+/// shared between threads if the elements can also be shared, since a shared
+/// reference gives access to the readable elements, and keeps the ring buffer
+/// alive through `Consumer::closed` or `Consumer::waiter` like
+/// [`Producer`].
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (_producer, consumer) = create_ring_buffer::<*const u8>(1);
+/// std::thread::spawn(move || drop(consumer));
+/// ```
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (_producer, consumer) = create_ring_buffer::<*const u8>(1);
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let _ = consumer.peek_element();
+///     });
+/// });
+/// ```
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// // Can be shared between threads, but not sent to another thread.
+/// struct NotSend(*const u8);
+/// unsafe impl Sync for NotSend {}
+///
+/// let (_producer, consumer) = create_ring_buffer::<NotSend>(1);
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let _ = consumer.available();
+///     });
+/// });
+/// ```
+pub struct Consumer<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
+    index: usize,
+    read: u64,
+    /// Write position last loaded from the buffer, which may lag behind.
+    cached_write_pos: usize,
+}
+
+impl<T, I: Index> Consumer<T, I> {
+    /// Returns the number of elements available for reading.
+    ///
+    /// This method returns the number of elements available for reading.
+    ///
+    /// # Returns
+    ///
+    /// Number of elements available for reading.
+    ///
+    /// # Example
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (_, consumer) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(consumer.available(), 0);
+    /// ```
+    pub fn available(&self) -> usize {
+        self.buffer.available_read()
+    }
+
+    /// Returns an estimate of the number of elements available for reading.
+    ///
+    /// This method behaves like [`Consumer::available`], but loads the
+    /// positions with `Relaxed` ordering, so the value may be stale. It is
+    /// intended as a cheap heuristic, for example for sizing the next batch in
+    /// a polling loop; the read methods check the available elements again.
+    ///
+    /// # Returns
+    ///
+    /// An estimate of the number of elements available for reading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2]);
+    /// assert_eq!(consumer.available_hint(), 2);
+    /// ```
+    pub fn available_hint(&self) -> usize {
+        self.buffer.readable(Ordering::Relaxed)
+    }
+
+    /// Returns the number of elements available for reading before the wrap-around.
+    ///
+    /// This method returns how many elements can be read contiguously, that
+    /// is, the size of the first region the closure of
+    /// [`Consumer::read_slices`] receives.
+    ///
+    /// # Returns
+    ///
+    /// Number of elements available for reading up to the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// consumer.advance(3);
+    /// producer.write_exact(&[5, 6]);
+    /// assert_eq!(consumer.available(), 3);
+    /// assert_eq!(consumer.contiguous_available(), 2);
+    /// ```
+    pub fn contiguous_available(&self) -> usize {
+        self.buffer
+            .contiguous_available(self.index, self.available())
+    }
+
+    /// Returns the capacity of the ring buffer.
+    ///
+    /// This method returns the total number of elements the ring buffer can
+    /// hold, which is the size specified when the buffer was created.
+    ///
+    /// # Returns
+    ///
+    /// Capacity of the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (_, consumer) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(consumer.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns `true` if the ring buffer contains no elements.
+    ///
+    /// This method returns `true` if there are no elements available for
+    /// reading.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the ring buffer is empty, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(2);
+    /// assert!(consumer.is_empty());
+    /// producer.write_element(1);
+    /// assert!(!consumer.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns `true` if the ring buffer is full.
+    ///
+    /// This method returns `true` if there is no space available for writing.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the ring buffer is full, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(2);
+    /// assert!(!consumer.is_full());
+    /// producer.write_element(1);
+    /// producer.write_element(2);
+    /// assert!(consumer.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    /// Returns `true` if the producer has not been dropped.
+    ///
+    /// Once the producer has been dropped and the elements left have been
+    /// read, nothing more will come, so a consumer waiting for elements can
+    /// stop instead of retrying forever. The elements written before the
+    /// producer was dropped stay readable.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the producer is alive, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// assert!(consumer.is_producer_alive());
+    /// producer.write_element(1);
+    /// drop(producer);
+    /// assert!(!consumer.is_producer_alive());
+    /// assert_eq!(consumer.read_element(), Some(1));
+    /// ```
+    pub fn is_producer_alive(&self) -> bool {
+        !self.buffer.is_producer_dropped()
+    }
+
+    /// Returns how full the ring buffer is, as a ratio of its capacity.
+    ///
+    /// This method returns the number of elements available for reading
+    /// divided by the capacity, ranging from `0.0` (empty) to `1.0` (full).
+    /// A buffer with zero capacity always returns `0.0`.
+    ///
+    /// # Returns
+    ///
+    /// The fill ratio of the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2]);
+    /// assert_eq!(consumer.fill_ratio(), 0.5);
+    /// ```
+    pub fn fill_ratio(&self) -> f32 {
+        self.buffer.fill_ratio()
+    }
+
+    /// Returns the total number of elements ever written to the ring buffer.
+    ///
+    /// This method returns a monotonic count of the elements written by the
+    /// producer since the ring buffer was created. The difference from
+    /// [`Consumer::total_read`] is the number of elements available for
+    /// reading.
+    ///
+    /// # Returns
+    ///
+    /// The total number of elements written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// consumer.read_element();
+    /// assert_eq!(consumer.total_written(), 3);
+    /// ```
+    pub fn total_written(&self) -> u64 {
+        self.read + self.buffer.available_read() as u64
+    }
+
+    /// Returns the total number of elements ever read from the ring buffer.
+    ///
+    /// This method returns a monotonic count of the elements read since the
+    /// ring buffer was created, which can be used as a stream position, for
+    /// example a sample clock.
+    ///
+    /// # Returns
+    ///
+    /// The total number of elements read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// for value in 0..5 {
+    ///     producer.write_element(value);
+    ///     consumer.read_element();
+    /// }
+    /// assert_eq!(consumer.total_read(), 5);
+    /// ```
+    pub fn total_read(&self) -> u64 {
+        self.read
+    }
+
+    /// Reads elements from the ring buffer.
+    ///
+    /// This method reads elements from the ring buffer using the provided closure.
+    /// The closure `f` receives a slice of readable elements and the current
+    /// offset within the read operation, and it should return the number of elements
+    /// read. The `max_size` parameter specifies the maximum number of elements to
+    /// read. If `None`, the method attempts to read as many elements as available.
+    ///
+    /// If there are no elements available for reading, the function returns
+    /// immediately without blocking, and the closure is not called.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable elements. It takes a reference
+    ///   to a slice of readable elements and an offset as arguments, and
+    ///   returns the number of elements read. The closure will not be called if
+    ///   there are no readable elements. If the buffer wraps around, the closure
+    ///   may be called twice. The slice passed to the closure contains the
+    ///   currently accessible elements. The offset is `0` for the first call
+    ///   and increases by the number of elements read in subsequent calls. If
+    ///   the closure returns a value less than the length of the slice passed to
+    ///   it, it is considered as an interruption of the read operation by that
+    ///   number of elements.
+    ///   The closure must not return a value greater than the length of the
+    ///   slice; otherwise the method panics.
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to read. If `None`, the method will read up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_slices(|data, offset| {
+    ///     assert_eq!(data.len(), 5);
+    ///     data[..2].copy_from_slice(&[1, 2]);
+    ///     2
+    /// }, None);
+    /// consumer.read_slices(|data, offset| {
+    ///     assert_eq!(data.len(), 2);
+    ///     assert_eq!(offset, 0);
+    ///     2
+    /// }, None);
+    /// producer.write_slices(|data, offset| {
+    ///     data.copy_from_slice(&([3, 4, 5, 6, 7][offset..offset + data.len()]));
+    ///     data.len()
+    /// }, None);
+    /// consumer.read_slices(|data, offset| {
+    ///     assert_eq!(data, &([3, 4, 5, 6, 7][offset..offset + data.len()]));
+    ///     data.len()
+    /// }, None);
+    ///
+    /// ```
+    pub fn read_slices(
+        &mut self,
+        f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize {
+        self.read_slices_chunked(f, max_size, usize::MAX)
+    }
+
+    /// Reads elements from the ring buffer, telling why none was read.
+    ///
+    /// This method behaves like [`Consumer::read_slices`], but fails instead of
+    /// returning 0 when no element is available, distinguishing a buffer that
+    /// is empty for now from one that is empty for good because the producer
+    /// has been dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable data and returns the
+    ///   number of elements read.
+    /// * `max_size` - An optional parameter specifying the maximum number of
+    ///   elements to read. If `None`, the method will read up to the available
+    ///   elements.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the number of elements read, or `Err` with
+    /// [`TryReadError::Empty`] if the buffer is empty, or
+    /// [`TryReadError::Closed`] if it is empty and the producer has been
+    /// dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer, TryReadError};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// drop(producer);
+    /// let mut received = Vec::new();
+    /// loop {
+    ///     match consumer.try_read_slices(|data, _| {
+    ///         received.extend_from_slice(data);
+    ///         data.len()
+    ///     }, None) {
+    ///         Ok(_) | Err(TryReadError::Empty) => continue,
+    ///         Err(TryReadError::Closed) => break,
+    ///     }
+    /// }
+    /// assert_eq!(received, [1, 2, 3]);
+    /// ```
+    pub fn try_read_slices(
+        &mut self,
+        f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> Result<usize, TryReadError> {
+        // Checked first, so that the elements written before the drop are seen.
+        let closed = self.buffer.is_producer_dropped();
+        if self.available_cached(1) == 0 {
+            return Err(if closed {
+                TryReadError::Closed
+            } else {
+                TryReadError::Empty
+            });
+        }
+        Ok(self.read_slices(f, max_size))
+    }
+
+    /// Reads elements from the ring buffer in chunks of limited length.
+    ///
+    /// This method behaves like [`Consumer::read_slices`], but the slice passed
+    /// to the closure never exceeds `max_chunk` elements. The closure is called
+    /// repeatedly until `max_size` elements (or all available elements) have
+    /// been read, which keeps each closure invocation short.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable elements. See
+    ///   [`Consumer::read_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to read. If `None`, the method will read up to the number of
+    ///   available elements.
+    /// * `max_chunk` - The maximum length of the slice passed to the closure.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chunk` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(10);
+    /// producer.write_exact(&[1, 2, 3, 4, 5]);
+    /// let mut chunks = Vec::new();
+    /// assert_eq!(consumer.read_slices_chunked(|data, _| {
+    ///     chunks.push(data.to_vec());
+    ///     data.len()
+    /// }, None, 2), 5);
+    /// assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    /// ```
+    pub fn read_slices_chunked(
+        &mut self,
+        mut f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+        max_chunk: usize,
+    ) -> usize {
+        assert!(max_chunk > 0, "max_chunk must be greater than 0");
+        self.read_slices_with(
+            |data, offset| ControlFlow::Continue(f(data, offset)),
+            max_size,
+            max_chunk,
+        )
+    }
+
+    /// Common process of the slice-based read operations.
+    fn read_slices_with(
+        &mut self,
+        mut f: impl FnMut(&[T], usize) -> ControlFlow<usize, usize>,
+        max_size: Option<usize>,
+        max_chunk: usize,
+    ) -> usize {
+        let available = self.available_cached(max_size.unwrap_or(usize::MAX));
+        let read = &mut self.read;
+        self.buffer.process_slices(
+            &mut self.index,
+            available,
+            |buf, len, process_offset| {
+                f(
+                    // No boundaries are crossed.
+                    unsafe { from_raw_parts(buf, len) },
+                    process_offset,
+                )
+            },
+            max_size,
+            max_chunk,
+            false,
+            |buffer, start, processed| {
+                // The processed elements are consumed, so they are dropped here.
+                buffer.consume(start, processed);
+                *read += processed as u64;
+            },
+        )
+    }
+
+    /// Reads elements from the ring buffer if enough elements are available.
+    ///
+    /// This method behaves like [`Consumer::read_slices`], but only if at least
+    /// `min_size` elements are available for reading. Otherwise, it returns `0`
+    /// immediately without calling the closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_size` - The minimum number of elements that must be available for
+    ///   reading.
+    /// * `f` - A closure that processes the readable elements. See
+    ///   [`Consumer::read_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to read. If `None`, the method will read up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_slices_at_least(4, |_, _| unreachable!(), None), 0);
+    /// producer.write_element(4);
+    /// assert_eq!(consumer.read_slices_at_least(4, |data, _| {
+    ///     assert_eq!(data, &[1, 2, 3, 4]);
+    ///     data.len()
+    /// }, None), 4);
+    /// ```
+    pub fn read_slices_at_least(
+        &mut self,
+        min_size: usize,
+        f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize {
+        if self.available_cached(min_size) < min_size {
+            return 0;
+        }
+        self.read_slices(f, max_size)
+    }
+
+    /// Reads elements from the ring buffer until the closure breaks with a result.
+    ///
+    /// This method behaves like [`Consumer::read_slices`], but the closure
+    /// returns the number of elements read together with a `ControlFlow`.
+    /// Returning `ControlFlow::Break(value)` stops the operation after the read
+    /// elements have been consumed, and `value` is handed back to the caller.
+    /// This allows a parser to stop in the middle of the stream and return the
+    /// decoded value.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable elements. It takes a reference
+    ///   to a slice of readable elements and an offset, and returns the number of
+    ///   elements read and whether to continue the operation. See
+    ///   [`Consumer::read_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to read. If `None`, the method will read up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the number of elements read and the value passed to
+    /// `ControlFlow::Break`, or `None` if the closure did not break.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::ops::ControlFlow;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(b"ab\ncd");
+    ///
+    /// // Reads a line terminated by `\n`.
+    /// let result = consumer.read_slices_until(|data, _| {
+    ///     match data.iter().position(|&b| b == b'\n') {
+    ///         Some(pos) => (pos + 1, ControlFlow::Break(data[..pos].to_vec())),
+    ///         None => (0, ControlFlow::Continue(())),
+    ///     }
+    /// }, None);
+    /// assert_eq!(result, (3, Some(b"ab".to_vec())));
+    /// assert_eq!(consumer.available(), 2);
+    /// ```
+    pub fn read_slices_until<R>(
+        &mut self,
+        mut f: impl FnMut(&[T], usize) -> (usize, ControlFlow<R>),
+        max_size: Option<usize>,
+    ) -> (usize, Option<R>) {
+        let mut result = None;
+        let read = self.read_slices_with(
+            |data, offset| match f(data, offset) {
+                (read, ControlFlow::Continue(())) => ControlFlow::Continue(read),
+                (read, ControlFlow::Break(value)) => {
+                    result = Some(value);
+                    ControlFlow::Break(read)
+                }
+            },
+            max_size,
+            usize::MAX,
+        );
+        (read, result)
+    }
+
+    /// Reads elements from the ring buffer using a fallible closure.
+    ///
+    /// This method behaves like [`Consumer::read_slices`], but the closure
+    /// returns a `Result`. If the closure returns an error, the operation stops,
+    /// the elements read by the previous invocations are consumed, and the error
+    /// is returned together with the number of those elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable elements. It takes a reference
+    ///   to a slice of readable elements and an offset, and returns the number of
+    ///   elements read or an error. See [`Consumer::read_slices`].
+    /// * `max_size` - An optional parameter specifying the maximum number of elements
+    ///   to read. If `None`, the method will read up to the number of
+    ///   available elements.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the number of elements read, or `Err` containing the
+    /// number of elements read before the error and the error itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::io::Write;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// let mut writer = Vec::new();
+    /// assert_eq!(consumer.read_slices_result(|data, _| writer.write(data), None).unwrap(), 3);
+    /// assert_eq!(writer, vec![1, 2, 3]);
+    /// ```
+    pub fn read_slices_result<E>(
+        &mut self,
+        mut f: impl FnMut(&[T], usize) -> Result<usize, E>,
+        max_size: Option<usize>,
+    ) -> Result<usize, (usize, E)> {
+        let mut error = None;
+        let read = self.read_slices_with(
+            |data, offset| match f(data, offset) {
+                Ok(read) => ControlFlow::Continue(read),
+                Err(e) => {
+                    error = Some(e);
+                    ControlFlow::Break(0)
+                }
+            },
+            max_size,
+            usize::MAX,
+        );
+        match error {
+            None => Ok(read),
+            Some(e) => Err((read, e)),
+        }
+    }
+
+    /// Reads elements from the ring buffer. (Deprecated)
+    ///
+    /// This method reads elements from the ring buffer using the provided closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the readable elements.
+    /// * `max_size` - An optional parameter specifying the maximum number of elements to read.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read.
+    #[deprecated(note = "Please use `read_slices` instead")]
+    pub fn read(
+        &mut self,
+        f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize {
+        self.read_slices(f, max_size)
+    } 
+   
+    /// Grants access to a contiguous readable region of the ring buffer.
+    ///
+    /// This method reserves up to `max` readable elements from the current read
+    /// position up to the end of the buffer, and returns them as a
+    /// [`ReadGrant`]. Unlike the closure-based methods, the grant can be passed
+    /// to external code across multiple statements. The elements are consumed
+    /// only after calling [`ReadGrant::release`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of elements to grant.
+    ///
+    /// # Returns
+    ///
+    /// A `ReadGrant` over the readable region, or `None` if no elements can be
+    /// read or `max` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    /// use std::io::Write;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// let mut writer = [0u8; 2];
+    /// let grant = consumer.read_grant(5).unwrap();
+    /// let len = (&mut writer[..]).write(&grant).unwrap();
+    /// grant.release(len);
+    /// assert_eq!(writer, [1, 2]);
+    /// assert_eq!(consumer.available(), 1);
+    /// ```
+    pub fn read_grant(&mut self, max: usize) -> Option<ReadGrant<'_, T, I>> {
+        ReadGrant::new(self, max)
+    }
+
+    /// Reads a single element from the ring buffer.
+    ///
+    /// This method reads a single element from the ring buffer and returns it. If the
+    /// buffer is empty, it returns `None`. The element is moved out of the buffer,
+    /// so `T` is not required to implement `Copy`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the element if available, or `None` if the buffer is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_slices(|data, offset| {
+    ///     data.copy_from_slice(&([3, 4, 5, 6, 7][offset..offset + data.len()]));
+    ///     data.len()
+    /// }, None);
+    /// assert_eq!(consumer.read_element(), Some(3));
+    /// assert_eq!(consumer.read_element(), Some(4));
+    /// assert_eq!(consumer.read_element(), Some(5));
+    /// assert_eq!(consumer.read_element(), Some(6));
+    /// assert_eq!(consumer.read_element(), Some(7));
+    /// assert_eq!(consumer.read_element(), None);
+    /// ```
+    pub fn read_element(&mut self) -> Option<T> {
+        if self.available_cached(1) == 0 {
+            return None;
+        }
+        let element = self.buffer.read_element(&mut self.index);
+        self.read += 1;
+        Some(element)
+    }
+
+    /// Reads a single element from the ring buffer, telling why none was read.
+    ///
+    /// This method behaves like [`Consumer::read_element`], but distinguishes a
+    /// buffer that is empty for now from one that is empty for good because
+    /// the producer has been dropped. The elements written before the producer
+    /// was dropped are still read first.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the element if available, or `Err` with
+    /// [`TryReadError::Empty`] if the buffer is empty, or
+    /// [`TryReadError::Closed`] if it is empty and the producer has been
+    /// dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer, TryReadError};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(2);
+    /// assert_eq!(consumer.try_read_element(), Err(TryReadError::Empty));
+    /// producer.write_element(1);
+    /// drop(producer);
+    /// assert_eq!(consumer.try_read_element(), Ok(1));
+    /// assert_eq!(consumer.try_read_element(), Err(TryReadError::Closed));
+    /// ```
+    pub fn try_read_element(&mut self) -> Result<T, TryReadError> {
+        // Checked first, so that the elements written before the drop are seen.
+        let closed = self.buffer.is_producer_dropped();
+        self.read_element().ok_or(if closed {
+            TryReadError::Closed
+        } else {
+            TryReadError::Empty
+        })
+    }
+
+    /// Reads elements from the ring buffer into a slice.
+    ///
+    /// This method copies up to `dst.len()` elements from the ring buffer into
+    /// `dst`, handling the wrap-around of the buffer internally. If fewer
+    /// elements are available, only the available elements are copied.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to copy the read elements into.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_slices(|data, offset| {
+    ///     data[..3].copy_from_slice(&[1, 2, 3]);
+    ///     3
+    /// }, None);
+    ///
+    /// let mut buf = [0; 5];
+    /// assert_eq!(consumer.read_into_slice(&mut buf), 3);
+    /// assert_eq!(buf, [1, 2, 3, 0, 0]);
+    /// assert_eq!(consumer.read_into_slice(&mut buf), 0);
+    /// ```
+    pub fn read_into_slice(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let len = dst.len().min(self.available_cached(dst.len()));
+        if len == 0 {
+            return 0;
+        }
+        let start = self.index;
+        // The elements are readable and `T` is `Copy`.
+        unsafe { self.buffer.copy_to_slice(start, &mut dst[..len]) };
+        self.buffer.wraparound_index(&mut self.index, len);
+        self.buffer.consume(start, len);
+        self.read += len as u64;
+        len
+    }
+
+    /// Reads exactly `dst.len()` elements from the ring buffer, or none at all.
+    ///
+    /// This method fills the entire slice only if at least `dst.len()` elements
+    /// are available for reading. Otherwise, nothing is read and the buffer is
+    /// left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to copy the read elements into.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `dst` was filled, `false` if not enough elements were available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    ///
+    /// let mut buf = [0; 2];
+    /// assert!(consumer.read_exact_into(&mut buf));
+    /// assert_eq!(buf, [1, 2]);
+    /// assert!(!consumer.read_exact_into(&mut buf)); // Only one element left
+    /// assert_eq!(consumer.available(), 1);
+    /// ```
+    pub fn read_exact_into(&mut self, dst: &mut [T]) -> bool
+    where
+        T: Copy,
+    {
+        if self.available_cached(dst.len()) < dst.len() {
+            return false;
+        }
+        self.read_into_slice(dst);
+        true
+    }
+
+    /// Reads exactly `N` elements from the ring buffer as an array, or none at all.
+    ///
+    /// This method reads `N` elements only if at least `N` elements are available
+    /// for reading. Otherwise, nothing is read and the buffer is left untouched.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the array of read elements, or `None` if fewer
+    /// than `N` elements were available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_array::<2>(), Some([1, 2]));
+    /// assert_eq!(consumer.read_array::<2>(), None); // Only one element left
+    /// assert_eq!(consumer.available(), 1);
+    /// ```
+    pub fn read_array<const N: usize>(&mut self) -> Option<[T; N]>
+    where
+        T: Copy,
+    {
+        if self.available_cached(N) < N {
+            return None;
+        }
+        let (first, second) = self.as_slices();
+        let array = core::array::from_fn(|i| {
+            if i < first.len() {
+                first[i]
+            } else {
+                second[i - first.len()]
+            }
+        });
+        self.advance(N);
+        Some(array)
+    }
+
+    /// Returns an iterator that reads elements from the ring buffer.
+    ///
+    /// The returned iterator yields elements by value, consuming them from the
+    /// ring buffer, until the buffer is empty. Elements written by the producer
+    /// while iterating are also yielded.
+    ///
+    /// # Returns
+    ///
+    /// A `PopIter` reading elements from the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// assert_eq!(consumer.pop_iter().take(2).collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![3, 4]);
+    /// assert_eq!(consumer.pop_iter().next(), None);
+    /// ```
+    pub fn pop_iter(&mut self) -> PopIter<'_, T, I> {
+        PopIter { consumer: self }
+    }
+
+    /// Returns an iterator that drains the elements currently in the ring buffer.
+    ///
+    /// The returned iterator yields exactly the elements that were available
+    /// for reading when this method was called, freeing space as it goes.
+    /// Elements written by the producer after the call are not yielded. When the
+    /// iterator is dropped, any remaining elements of the snapshot are removed
+    /// from the buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Drain` over the currently readable elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// let mut drain = consumer.drain();
+    /// assert_eq!(drain.next(), Some(1));
+    /// producer.write_element(4);
+    /// assert_eq!(drain.collect::<Vec<_>>(), vec![2, 3]);
+    ///
+    /// producer.write_exact(&[5, 6]);
+    /// consumer.drain().next(); // Remaining elements are removed on drop
+    /// assert_eq!(consumer.available(), 0);
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, I> {
+        Drain {
+            remaining: self.available(),
+            consumer: self,
+        }
+    }
+
+    /// Returns an iterator over the readable elements without consuming them.
+    ///
+    /// The returned iterator walks the elements that were available for reading
+    /// when this method was called, from the oldest to the newest, handling the
+    /// wrap-around of the buffer seamlessly.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over references to the readable elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert_eq!(consumer.available(), 3);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (first, second) = self.buffer.readable_slices(self.index);
+        first.iter().chain(second)
+    }
+
+    /// Returns a reference to a readable element without consuming it.
+    ///
+    /// This method provides indexed access to the readable elements, where index
+    /// `0` is the oldest element (the one [`Consumer::read_element`] would
+    /// return next).
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the element, relative to the oldest readable element.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a reference to the element, or `None` if `i` is
+    /// not less than the number of readable elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.get(0), Some(&1));
+    /// assert_eq!(consumer.get(2), Some(&3));
+    /// assert_eq!(consumer.get(3), None);
+    /// ```
+    pub fn get(&self, i: usize) -> Option<&T> {
+        let (first, second) = self.as_slices();
+        if i < first.len() {
+            Some(&first[i])
+        } else {
+            second.get(i - first.len())
+        }
+    }
+
+    /// Returns the readable elements as two slices without consuming them.
+    ///
+    /// The first slice contains the elements from the oldest one up to the end
+    /// of the buffer, and the second slice contains the elements that wrapped
+    /// around to the beginning of the buffer. The second slice is empty if the
+    /// readable elements do not wrap around. Use [`Consumer::advance`] to
+    /// consume the elements once they have been processed.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of two slices containing the readable elements in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// consumer.advance(3);
+    /// producer.write_exact(&[5, 6, 7]);
+    /// assert_eq!(consumer.as_slices(), (&[4, 5][..], &[6, 7][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.buffer.readable_slices(self.index)
+    }
+
+    /// Returns the base pointer of the ring buffer storage.
+    ///
+    /// This method, together with [`Consumer::readable_regions`], allows code
+    /// outside of Rust, such as C audio drivers or DMA controllers, to read
+    /// directly from the storage. The read elements are freed only after
+    /// calling [`Consumer::advance`].
+    ///
+    /// # Returns
+    ///
+    /// A pointer to the first element of the storage. The storage holds
+    /// [`Consumer::capacity`] elements and is never reallocated while the
+    /// producer or the consumer is alive.
+    ///
+    /// Reading through the pointer is allowed only within the regions returned
+    /// by [`Consumer::readable_regions`], and only until they are passed to
+    /// [`Consumer::advance`].
+    pub fn as_ptr(&self) -> *const T {
+        self.buffer.as_mut_ptr()
+    }
+
+    /// Returns the readable elements of the ring buffer as two regions.
+    ///
+    /// This method is the raw-parts counterpart of [`Consumer::as_slices`]. The
+    /// first region starts at the oldest element, and the second region starts
+    /// at the beginning of the storage and is empty if the readable elements do
+    /// not wrap around.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of two regions relative to [`Consumer::as_ptr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer, Region};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3, 4]);
+    /// consumer.advance(3);
+    /// producer.write_exact(&[5, 6]);
+    /// let (first, second) = consumer.readable_regions();
+    /// assert_eq!(first, Region { offset: 3, len: 2 });
+    /// assert_eq!(second, Region { offset: 0, len: 1 });
+    ///
+    /// // Stands in for a C driver reading from the storage.
+    /// let base = consumer.as_ptr();
+    /// assert_eq!(unsafe { base.add(second.offset).read() }, 6);
+    /// assert_eq!(consumer.advance(first.len + second.len), 3);
+    /// ```
+    pub fn readable_regions(&self) -> (Region, Region) {
+        self.buffer.regions(self.index, self.available())
+    }
+
+    /// Consumes elements from the ring buffer without reading them.
+    ///
+    /// This method removes up to `n` elements from the ring buffer, freeing their
+    /// space for the producer. It is typically used after inspecting the elements
+    /// with [`Consumer::as_slices`].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of elements to consume.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements consumed, which is `n` or the number of available
+    /// elements, whichever is smaller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.advance(2), 2);
+    /// assert_eq!(consumer.read_element(), Some(3));
+    /// assert_eq!(consumer.advance(2), 0);
+    /// ```
+    pub fn advance(&mut self, n: usize) -> usize {
+        self.read_slices(|data, _| data.len(), Some(n))
+    }
+
+    /// Discards the backlog, keeping only the newest elements.
+    ///
+    /// This method consumes all available elements except the last `keep`, so
+    /// that a consumer that fell behind, such as a real-time visualization,
+    /// jumps to the most recent data instead of going through stale elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - The number of the newest elements to keep.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(consumer.resync_to_newest(2), 3);
+    /// assert_eq!(consumer.pop_iter().collect::<Vec<_>>(), vec![4, 5]);
+    /// assert_eq!(consumer.resync_to_newest(2), 0);
+    /// ```
+    pub fn resync_to_newest(&mut self, keep: usize) -> usize {
+        let backlog = self.available().saturating_sub(keep);
+        self.advance(backlog)
+    }
+
+    /// Reads the newest element, discarding all older ones.
+    ///
+    /// This method behaves like [`Consumer::resync_to_newest`] keeping a
+    /// single element, and then reads it.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the newest element if available, or `None` if
+    /// the buffer is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_latest(), Some(3));
+    /// assert_eq!(consumer.read_latest(), None);
+    /// ```
+    pub fn read_latest(&mut self) -> Option<T> {
+        self.resync_to_newest(1);
+        self.read_element()
+    }
+
+    /// Peeks at the next element in the ring buffer without consuming it.
+    ///
+    /// This method returns a reference to the oldest element in the ring buffer
+    /// without removing it. If the buffer is empty, it returns `None`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a reference to the next element if available, or
+    /// `None` if the buffer is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
+    /// assert_eq!(consumer.peek_element(), None);
+    /// producer.write_element(1);
+    /// producer.write_element(2);
+    /// assert_eq!(consumer.peek_element(), Some(&1));
+    /// assert_eq!(consumer.peek_element(), Some(&1));
+    /// assert_eq!(consumer.read_element(), Some(1));
+    /// assert_eq!(consumer.peek_element(), Some(&2));
+    /// ```
+    pub fn peek_element(&self) -> Option<&T> {
+        self.buffer.peek_element(self.index)
+    }
+
+    /// Returns the number of elements available for reading, loading the write
+    /// position only if fewer than `needed` elements look readable.
+    #[inline]
+    fn available_cached(&mut self, needed: usize) -> usize {
+        self.buffer
+            .available_read_cached(&mut self.cached_write_pos, needed)
+    }
+
+    /// Discards the cached write position, which must be loaded again before
+    /// the next read.
+    fn invalidate_cache(&mut self) {
+        self.cached_write_pos = self.buffer.read_pos.load(Ordering::Relaxed);
+    }
+}
+
+impl<T, I: Index> Drop for Consumer<T, I> {
+    fn drop(&mut self) {
+        // Lets the ring buffer drop the unread elements when it is dropped.
+        self.buffer.read_index.store(self.index, Ordering::Relaxed);
+        self.buffer.consumer_dropped.store(true, PROGRESS);
+        self.buffer.wake_producer();
+        #[cfg(feature = "async")]
+        self.buffer.consumer_drop_waker.wake();
+    }
+}
+
+unsafe impl<T: Send, I: Index> Send for Consumer<T, I> {}
+unsafe impl<T: Send + Sync, I: Index> Sync for Consumer<T, I> {}
+
+impl<T, I: Index> fmt::Debug for Consumer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("capacity", &self.capacity())
+            .field("index", &self.index)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Iterator reading elements from the ring buffer.
+///
+/// This struct is created by [`Consumer::pop_iter`].
+pub struct PopIter<'a, T, I: Index = usize> {
+    consumer: &'a mut Consumer<T, I>,
+}
+
+impl<T, I: Index> Iterator for PopIter<'_, T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.consumer.read_element()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.consumer.available(), None)
+    }
+}
+
+/// Draining iterator over the elements in the ring buffer.
+///
+/// This struct is created by [`Consumer::drain`].
+pub struct Drain<'a, T, I: Index = usize> {
+    consumer: &'a mut Consumer<T, I>,
+    remaining: usize,
+}
+
+impl<T, I: Index> Iterator for Drain<'_, T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.consumer.read_element()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, I: Index> ExactSizeIterator for Drain<'_, T, I> {}
+
+impl<T, I: Index> Drop for Drain<'_, T, I> {
+    fn drop(&mut self) {
+        self.consumer.advance(self.remaining);
+    }
+}
+
+struct DirectRingBuffer<T, I: Index> {
+    /// Storage allocated as a `Box<[MaybeUninit<T>]>`.
+    ///
+    /// Only the readable elements are initialized, unless `T` is `Copy`. It is
+    /// accessed through raw pointers only, since the producer and the consumer
+    /// access disjoint parts of it at the same time.
+    elements: NonNull<MaybeUninit<T>>,
+    capacity: usize,
+    /// `true` if the capacity is a power of two, so that the indices wrap
+    /// around with a bitmask.
+    masked: bool,
+    /// Alignment of the storage allocation, which is larger than the alignment
+    /// of `T` if the storage is over-aligned.
+    align: usize,
+    /// `true` if the storage is mapped twice back-to-back, so that the
+    /// elements never wrap around.
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    double_mapped: bool,
+    /// Position of the next element to be written, advanced by the producer.
+    ///
+    /// The positions increase monotonically and wrap around at the width of
+    /// the index type. They count the elements, and are mapped to the slots by
+    /// the indices local to the handles. Each position is stored only by its
+    /// owner and loaded by the other side, so the producer and the consumer
+    /// never modify the same atomic. They are kept on separate cache lines,
+    /// away from the fields that are only read.
+    write_pos: CachePadded<Counter<I>>,
+    /// Position of the next element to be read, advanced by the consumer.
+    ///
+    /// In overwrite mode, the producer advances it as well to evict the oldest
+    /// elements, so both sides advance it with compare-and-swap.
+    read_pos: CachePadded<Counter<I>>,
+    /// Position of the next element to be read by the observer.
+    observer_pos: CachePadded<Counter<I>>,
+    observer_attached: AtomicBool,
+    /// Index of the first readable element, stored when the consumer is dropped.
+    read_index: Counter<I>,
+    /// Accesses to the slots, tracked when model checking.
+    slots: Slots,
+    /// `true` if the producer evicts the oldest elements when the buffer is
+    /// full. See [`RingBufferBuilder::build_overwriting`].
+    overwrite: bool,
+    /// Scrubs an element before it is dropped, if the storage is zeroized.
+    #[cfg(feature = "zeroize")]
+    zeroize: Option<fn(&mut T)>,
+    /// Thread of the producer waiting for space.
+    #[cfg(feature = "std-blocking")]
+    space_waiter: blocking::WaitSlot,
+    /// Thread of the consumer waiting for elements.
+    #[cfg(feature = "std-blocking")]
+    data_waiter: blocking::WaitSlot,
+    /// Thread registered by a [`Waiter`] of the producer, kept apart from
+    /// `space_waiter` so that neither registration replaces the other.
+    #[cfg(feature = "std-blocking")]
+    space_armed: blocking::WaitSlot,
+    /// Thread registered by a [`Waiter`] of the consumer.
+    #[cfg(feature = "std-blocking")]
+    data_armed: blocking::WaitSlot,
+    /// Strategy of the waiting handles, or `None` for [`Park`].
+    #[cfg(feature = "std-blocking")]
+    wait_strategy: Option<std::sync::Arc<dyn WaitStrategy>>,
+    /// Task of the producer waiting for space.
+    #[cfg(feature = "async")]
+    space_waker: waker::WakerSlot,
+    /// Task of the consumer waiting for elements.
+    #[cfg(feature = "async")]
+    data_waker: waker::WakerSlot,
+    /// Notifier of the producer waiting for space.
+    #[cfg(all(feature = "mio", unix))]
+    space_notify: notify::NotifySlot,
+    /// Notifier of the consumer waiting for elements.
+    #[cfg(all(feature = "mio", unix))]
+    data_notify: notify::NotifySlot,
+    /// Task waiting for the producer to be dropped.
+    #[cfg(feature = "async")]
+    producer_drop_waker: waker::WakerSlot,
+    /// Task waiting for the consumer to be dropped.
+    #[cfg(feature = "async")]
+    consumer_drop_waker: waker::WakerSlot,
+    producer_dropped: AtomicBool,
+    consumer_dropped: AtomicBool,
+}
+
+impl<T, I: Index> DirectRingBuffer<T, I> {
+    /// Creates a buffer whose first `used` elements are readable.
+    fn new(elements: Box<[MaybeUninit<T>]>, used: usize) -> Self {
+        let capacity = elements.len();
+        let elements = unsafe { NonNull::new_unchecked(Box::into_raw(elements).cast()) };
+        // The storage is allocated like a `Box<[MaybeUninit<T>]>`.
+        unsafe { Self::from_raw_parts(elements, capacity, mem::align_of::<T>(), used) }
+    }
+
+    /// Creates an empty buffer whose storage is aligned to at least `align`
+    /// bytes, which must be a power of two.
+    fn with_alignment(capacity: usize, align: usize, zeroed: bool) -> Result<Self, CreateError> {
+        let align = align.max(mem::align_of::<T>());
+        let layout = Self::storage_layout(capacity, align).ok_or(CreateError::CapacityOverflow)?;
+        let elements = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            let elements = unsafe {
+                if zeroed {
+                    alloc_zeroed(layout)
+                } else {
+                    alloc(layout)
+                }
+            };
+            NonNull::new(elements.cast()).ok_or(CreateError::AllocationFailed)?
+        };
+        // The storage is allocated with the layout of `align`.
+        Ok(unsafe { Self::from_raw_parts(elements, capacity, align, 0) })
+    }
+
+    /// Creates an empty buffer whose storage is mapped twice back-to-back, and
+    /// aligned to at least `align` bytes and the page size.
+    ///
+    /// The size of the storage must be a multiple of the page size.
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    fn double_mapped(capacity: usize, align: usize) -> Result<Self, CreateError> {
+        let align = align.max(mem::align_of::<T>());
+        let size = capacity
+            .checked_mul(mem::size_of::<T>())
+            .filter(|&size| size <= isize::MAX as usize / 2)
+            .ok_or(CreateError::CapacityOverflow)?;
+        let elements = if size == 0 {
+            NonNull::dangling()
+        } else {
+            mapping::map(size, align)
+                .ok_or(CreateError::AllocationFailed)?
+                .cast()
+        };
+        // The storage is freed by `free_elements` according to `double_mapped`.
+        let mut buffer = unsafe { Self::from_raw_parts(elements, capacity, align, 0) };
+        buffer.double_mapped = true;
+        Ok(buffer)
+    }
+
+    /// Returns the layout of a storage of `capacity` elements aligned to
+    /// `align`, or `None` if its size overflows.
+    fn storage_layout(capacity: usize, align: usize) -> Option<Layout> {
+        Layout::array::<T>(capacity).ok()?.align_to(align).ok()
+    }
+
+    /// Creates a buffer from its storage, whose first `used` elements are
+    /// readable.
+    ///
+    /// # Safety
+    ///
+    /// The storage must be allocated with the layout returned by
+    /// [`Self::storage_layout`] for `capacity` and `align`, or be dangling if
+    /// the layout has a size of zero.
+    unsafe fn from_raw_parts(
+        elements: NonNull<MaybeUninit<T>>,
+        capacity: usize,
+        align: usize,
+        used: usize,
+    ) -> Self {
+        Self {
+            elements,
+            capacity,
+            masked: capacity.is_power_of_two(),
+            align,
+            #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+            double_mapped: false,
+            write_pos: CachePadded::new(Counter::new(used)),
+            read_pos: CachePadded::new(Counter::new(0)),
+            observer_pos: CachePadded::new(Counter::new(0)),
+            observer_attached: AtomicBool::new(false),
+            read_index: Counter::new(0),
+            slots: Slots::new(capacity),
+            overwrite: false,
+            #[cfg(feature = "zeroize")]
+            zeroize: None,
+            #[cfg(feature = "std-blocking")]
+            space_waiter: blocking::WaitSlot::default(),
+            #[cfg(feature = "std-blocking")]
+            data_waiter: blocking::WaitSlot::default(),
+            #[cfg(feature = "std-blocking")]
+            space_armed: blocking::WaitSlot::default(),
+            #[cfg(feature = "std-blocking")]
+            data_armed: blocking::WaitSlot::default(),
+            #[cfg(feature = "std-blocking")]
+            wait_strategy: None,
+            #[cfg(feature = "async")]
+            space_waker: waker::WakerSlot::default(),
+            #[cfg(feature = "async")]
+            data_waker: waker::WakerSlot::default(),
+            #[cfg(all(feature = "mio", unix))]
+            space_notify: notify::NotifySlot::default(),
+            #[cfg(all(feature = "mio", unix))]
+            data_notify: notify::NotifySlot::default(),
+            #[cfg(feature = "async")]
+            producer_drop_waker: waker::WakerSlot::default(),
+            #[cfg(feature = "async")]
+            consumer_drop_waker: waker::WakerSlot::default(),
+            producer_dropped: AtomicBool::new(false),
+            consumer_dropped: AtomicBool::new(false),
+        }
+    }
+
+    /// Takes the storage out of the buffer, leaving an empty one.
+    ///
+    /// The storage must not be over-aligned or double-mapped. The elements in
+    /// the storage are not dropped.
+    fn take_elements(&mut self) -> Box<[MaybeUninit<T>]> {
+        debug_assert!(!self.is_over_aligned(), "the storage is over-aligned");
+        debug_assert!(!self.is_double_mapped(), "the storage is double-mapped");
+        let elements = unsafe {
+            Box::from_raw(ptr::slice_from_raw_parts_mut(
+                self.elements.as_ptr(),
+                self.capacity,
+            ))
+        };
+        self.clear();
+        elements
+    }
+
+    /// Frees the storage of the buffer, leaving an empty one.
+    ///
+    /// The elements in the storage are not dropped.
+    fn free_elements(&mut self) {
+        #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+        if self.double_mapped {
+            let size = self.capacity * mem::size_of::<T>();
+            if size != 0 {
+                unsafe { mapping::unmap(self.elements.cast(), size) };
+            }
+            self.clear();
+            return;
+        }
+        if !self.is_over_aligned() {
+            drop(self.take_elements());
+            return;
+        }
+        if let Some(layout) = Self::storage_layout(self.capacity, self.align) {
+            if layout.size() != 0 {
+                unsafe { dealloc(self.elements.as_ptr().cast(), layout) };
+            }
+        }
+        self.clear();
+    }
+
+    /// Forgets the storage and the positions, leaving an empty buffer.
+    fn clear(&mut self) {
+        self.elements = NonNull::dangling();
+        self.capacity = 0;
+        self.masked = false;
+        self.align = mem::align_of::<T>();
+        #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+        {
+            self.double_mapped = false;
+        }
+        self.write_pos.store(0, Ordering::Relaxed);
+        self.read_pos.store(0, Ordering::Relaxed);
+    }
+
+    /// Advises the kernel to back the storage with transparent huge pages.
+    #[cfg(feature = "huge-pages")]
+    fn advise_huge_pages(&self) {
+        #[cfg(target_os = "linux")]
+        {
+            let size = self.capacity * mem::size_of::<T>();
+            if size != 0 {
+                // The advice is only a hint, so a failure is not an error.
+                unsafe { libc::madvise(self.as_mut_ptr().cast(), size, libc::MADV_HUGEPAGE) };
+            }
+        }
+    }
+
+    /// Asks the kernel to allocate the storage on a NUMA node.
+    #[cfg(all(feature = "numa", target_os = "linux"))]
+    fn bind_numa_node(&self, node: u32) {
+        // Memory policy constants from <numaif.h>.
+        const MPOL_PREFERRED: libc::c_long = 1;
+        const MPOL_MF_MOVE: libc::c_ulong = 1 << 1;
+
+        let size = self.capacity * mem::size_of::<T>();
+        if size == 0 {
+            return;
+        }
+        let bits = libc::c_ulong::BITS as usize;
+        let mut mask = alloc::vec![0 as libc::c_ulong; node as usize / bits + 1];
+        mask[node as usize / bits] = 1 << (node as usize % bits);
+        // The placement is only a preference, so a failure is not an error.
+        unsafe {
+            libc::syscall(
+                libc::SYS_mbind,
+                self.as_mut_ptr(),
+                size,
+                MPOL_PREFERRED,
+                mask.as_ptr(),
+                mask.len() * bits + 1,
+                MPOL_MF_MOVE,
+            )
+        };
+    }
+
+    /// Returns `true` if the storage is aligned beyond the alignment of `T`,
+    /// so that it cannot be handed out as a `Box` or a `Vec`.
+    #[inline]
+    fn is_over_aligned(&self) -> bool {
+        self.align > mem::align_of::<T>()
+    }
+
+    /// Returns `true` if the storage is mapped twice back-to-back.
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    #[inline]
+    fn is_double_mapped(&self) -> bool {
+        self.double_mapped
+    }
+
+    #[cfg(not(all(feature = "double-mapped", target_os = "linux")))]
+    #[inline]
+    fn is_double_mapped(&self) -> bool {
+        false
+    }
+
+    /// Returns the capacity of the buffer.
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of elements available for reading.
+    #[inline]
+    fn available_read(&self) -> usize {
+        self.readable(Ordering::Acquire)
+    }
+
+    /// Returns the number of elements available for reading, loading the
+    /// positions with `order`.
+    #[inline]
+    fn readable(&self, order: Ordering) -> usize {
+        // Loading the read position first keeps the write position from
+        // appearing behind it when both are advanced concurrently.
+        let read_pos = self.read_pos.load(order);
+        let write_pos = self.write_pos.load(order);
+        distance::<I>(read_pos, write_pos).min(self.capacity())
+    }
+
+    /// Returns the number of elements available for writing.
+    ///
+    /// While an observer is attached, the elements it has not read yet are not
+    /// writable either.
+    #[inline]
+    fn available_write(&self) -> usize {
+        self.writable(Ordering::Acquire)
+    }
+
+    /// Returns the number of elements available for writing, loading the
+    /// positions with `order`.
+    #[inline]
+    fn writable(&self, order: Ordering) -> usize {
+        let used = self.readable(order);
+        let used = if self.observer_attached.load(order) {
+            used.max(self.observable(order))
+        } else {
+            used
+        };
+        self.capacity() - used
+    }
+
+    /// Returns the number of elements available for writing, based on the read
+    /// position cached by the producer.
+    ///
+    /// The read position is loaded again, and `cached_read_pos` updated, only if
+    /// fewer than `needed` elements are writable according to the cached one,
+    /// or while an observer is attached. Since the consumer only advances the
+    /// read position, the cached one never overstates the writable space.
+    #[inline]
+    fn available_write_cached(&self, cached_read_pos: &mut usize, needed: usize) -> usize {
+        // Only the producer advances the write position.
+        let write_pos = self.write_pos.load(Ordering::Relaxed);
+        let observed = self.observer_attached.load(Ordering::Acquire);
+        if !observed {
+            let available = self.capacity() - distance::<I>(*cached_read_pos, write_pos);
+            if available >= needed {
+                return available;
+            }
+        }
+        *cached_read_pos = self.read_pos.load(Ordering::Acquire);
+        let used = distance::<I>(*cached_read_pos, write_pos);
+        let used = if observed {
+            used.max(self.available_observe())
+        } else {
+            used
+        };
+        self.capacity() - used
+    }
+
+    /// Returns the number of elements available for reading, based on the
+    /// write position cached by the consumer.
+    ///
+    /// The write position is loaded again, and `cached_write_pos` updated, only
+    /// if fewer than `needed` elements are readable according to the cached one.
+    #[inline]
+    fn available_read_cached(&self, cached_write_pos: &mut usize, needed: usize) -> usize {
+        // Only the consumer advances the read position.
+        let read_pos = self.read_pos.load(Ordering::Relaxed);
+        let available = distance::<I>(read_pos, *cached_write_pos);
+        if available >= needed {
+            return available;
+        }
+        *cached_write_pos = self.write_pos.load(Ordering::Acquire);
+        distance::<I>(read_pos, *cached_write_pos)
+    }
+
+    /// Returns the number of elements available for the observer.
+    #[inline]
+    fn available_observe(&self) -> usize {
+        self.observable(Ordering::Acquire)
+    }
+
+    /// Returns the number of elements available for the observer, loading the
+    /// positions with `order`.
+    #[inline]
+    fn observable(&self, order: Ordering) -> usize {
+        let observer_pos = self.observer_pos.load(order);
+        let write_pos = self.write_pos.load(order);
+        distance::<I>(observer_pos, write_pos).min(self.capacity())
+    }
+
+    /// Returns the number of the oldest unread elements whose reading frees
+    /// their space for writing, that is, whose space an attached observer does
+    /// not still hold as well.
+    ///
+    /// The observer only frees space concurrently, so the result stays valid.
+    #[inline]
+    fn evictable(&self) -> usize {
+        let readable = self.available_read();
+        if self.observer_attached.load(Ordering::Acquire) {
+            readable.saturating_sub(self.available_observe())
+        } else {
+            readable
+        }
+    }
+
+    /// Advances the read position of an overwriting ring buffer, so that
+    /// `needed` elements can be written.
+    ///
+    /// The consumer advances the read position concurrently, so the position is
+    /// advanced with compare-and-swap, and only by the elements the consumer
+    /// has not read in the meantime.
+    fn evict(&self, cached_read_pos: &mut usize, needed: usize) {
+        // Only the producer advances the write position.
+        let write_pos = self.write_pos.load(Ordering::Relaxed);
+        let mut read_pos = self.read_pos.load(Ordering::Acquire);
+        loop {
+            let used = distance::<I>(read_pos, write_pos);
+            let excess = (used + needed).saturating_sub(self.capacity());
+            if excess == 0 {
+                break;
+            }
+            match self.read_pos.advance_from(read_pos, excess) {
+                Ok(()) => {
+                    read_pos = read_pos.wrapping_add(excess);
+                    break;
+                }
+                Err(current) => read_pos = current,
+            }
+        }
+        *cached_read_pos = read_pos;
+    }
+
+    /// Makes written elements available for reading.
+    #[inline]
+    fn publish(&self, n: usize) {
+        debug_assert!(n <= self.available_write(), "used exceeds the capacity");
+        self.write_pos.add_owned(n, PROGRESS);
+        self.wake_consumer();
+    }
+
+    /// Retracts the newest written elements, which are not readable anymore.
+    #[inline]
+    fn retract(&self, n: usize) {
+        debug_assert!(
+            n <= self.available_read(),
+            "retracted more elements than used"
+        );
+        self.write_pos.sub_owned(n, Ordering::Release);
+    }
+
+    /// Frees read elements for writing.
+    #[inline]
+    fn release(&self, n: usize) {
+        debug_assert!(
+            n <= self.available_read(),
+            "released more elements than used"
+        );
+        self.read_pos.add_owned(n, PROGRESS);
+        self.wake_producer();
+    }
+
+    /// Frees elements read by the observer for writing.
+    #[inline]
+    fn release_observed(&self, n: usize) {
+        self.observer_pos.add_owned(n, PROGRESS);
+        self.wake_producer();
+    }
+
+    /// Wakes the producer waiting for space, if any.
+    #[inline]
+    fn wake_producer(&self) {
+        #[cfg(feature = "std-blocking")]
+        {
+            self.space_waiter.wake();
+            self.space_armed.wake();
+        }
+        #[cfg(feature = "async")]
+        self.space_waker.wake();
+        #[cfg(all(feature = "mio", unix))]
+        self.space_notify.notify();
+    }
+
+    /// Wakes the consumer waiting for elements, if any.
+    #[inline]
+    fn wake_consumer(&self) {
+        #[cfg(feature = "std-blocking")]
+        {
+            self.data_waiter.wake();
+            self.data_armed.wake();
+        }
+        #[cfg(feature = "async")]
+        self.data_waker.wake();
+        #[cfg(all(feature = "mio", unix))]
+        self.data_notify.notify();
+    }
+
+    /// Returns the strategy of the waiting handles.
+    #[cfg(feature = "std-blocking")]
+    #[inline]
+    fn wait_strategy(&self) -> &dyn WaitStrategy {
+        self.wait_strategy.as_deref().unwrap_or(&Park)
+    }
+
+    /// Returns `true` if the producer has been dropped.
+    #[inline]
+    fn is_producer_dropped(&self) -> bool {
+        self.producer_dropped.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the consumer has been dropped.
+    #[inline]
+    fn is_consumer_dropped(&self) -> bool {
+        self.consumer_dropped.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.available_read() == 0
+    }
+
+    /// Returns `true` if the buffer has no space for writing.
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.available_write() == 0
+    }
+
+    /// Returns the number of the available elements up to the end of the buffer.
+    ///
+    /// All of them are contiguous if the storage is double-mapped, since the
+    /// elements past the end are the ones at the beginning.
+    #[inline]
+    fn contiguous_available(&self, index: usize, available: usize) -> usize {
+        if self.is_double_mapped() {
+            available
+        } else {
+            (self.capacity() - index).min(available)
+        }
+    }
+
+    /// Returns the ratio of the readable elements to the capacity.
+    #[inline]
+    fn fill_ratio(&self) -> f32 {
+        match self.capacity() {
+            0 => 0.0,
+            capacity => self.available_read() as f32 / capacity as f32,
+        }
+    }
+
+    /// Returns a pointer to the first element of the buffer.
+    #[inline]
+    fn as_mut_ptr(&self) -> *mut T {
+        self.elements.as_ptr().cast()
+    }
+
+    /// Returns the `available` elements starting at `index` as two regions.
+    fn regions(&self, index: usize, available: usize) -> (Region, Region) {
+        let first_len = self.contiguous_available(index, available);
+        (
+            Region {
+                offset: index,
+                len: first_len,
             },
-            max_size,
-            |atomic, processed| {
-                atomic.fetch_sub(processed, Ordering::Release);
+            Region {
+                offset: 0,
+                len: available - first_len,
             },
         )
     }
 
-    /// Reads elements from the ring buffer. (Deprecated)
-    ///
-    /// This method reads elements from the ring buffer using the provided closure.
+    /// Updates the index to wrap around the buffer.
     ///
-    /// # Arguments
+    /// `advance` must not exceed the capacity.
+    #[inline]
+    fn wraparound_index(&self, index: &mut usize, advance: usize) {
+        *index = if self.masked {
+            (*index + advance) & (self.capacity - 1)
+        } else if *index + advance >= self.capacity {
+            *index + advance - self.capacity
+        } else {
+            *index + advance
+        }
+    }
+
+    /// Retracts up to `n` of the newest readable elements.
     ///
-    /// * `f` - A closure that processes the readable elements.
-    /// * `max_size` - An optional parameter specifying the maximum number of elements to read.
+    /// The consumer must not be accessing the buffer at the same time. Nothing
+    /// is retracted while an observer is attached, since it may be reading the
+    /// elements.
+    fn unwrite(&self, index: &mut usize, n: usize) -> usize {
+        if self.observer_attached.load(Ordering::Acquire) {
+            return 0;
+        }
+        let retracted = n.min(self.available_read());
+        if retracted != 0 {
+            *index = (*index + self.capacity() - retracted) % self.capacity();
+            self.discard(*index, retracted, Self::retract);
+        }
+        retracted
+    }
+
+    /// Discards all readable elements, starting at `read_index`.
     ///
-    /// # Returns
+    /// The consumer must not be accessing the buffer at the same time. Nothing
+    /// is discarded while an observer is attached.
+    fn reset(&self, read_index: usize) -> bool {
+        if self.observer_attached.load(Ordering::Acquire) {
+            return false;
+        }
+        self.consume(read_index, self.available_read());
+        true
+    }
+
+    /// Drops `n` readable elements starting at `start` and frees their space.
     ///
-    /// The number of elements read.
-    #[deprecated(note = "Please use `read_slices` instead")]
-    pub fn read(
-        &mut self,
-        f: impl FnMut(&[T], usize) -> usize,
-        max_size: Option<usize>,
-    ) -> usize {
-        self.read_slices(f, max_size)
-    } 
-   
-    /// Reads a single element from the ring buffer.
+    /// The space is freed even if dropping an element panics.
+    fn consume(&self, start: usize, n: usize) {
+        self.discard(start, n, Self::release);
+    }
+
+    /// Drops `n` readable elements starting at `start`, and calls `free` to
+    /// free their space even if dropping an element panics.
+    fn discard(&self, start: usize, n: usize, free: fn(&Self, usize)) {
+        struct Free<'a, T, I: Index>(
+            &'a DirectRingBuffer<T, I>,
+            usize,
+            fn(&DirectRingBuffer<T, I>, usize),
+        );
+
+        impl<T, I: Index> Drop for Free<'_, T, I> {
+            fn drop(&mut self) {
+                (self.2)(self.0, self.1);
+            }
+        }
+
+        let _free = Free(self, n, free);
+        unsafe { self.drop_elements(start, n) };
+    }
+
+    /// Drops `n` elements starting at `start`, wrapping around the end.
     ///
-    /// This method reads a single element from the ring buffer and returns it. If the
-    /// buffer is empty, it returns `None`.
+    /// # Safety
     ///
-    /// # Returns
+    /// The elements must be initialized and must not be accessed afterwards.
+    unsafe fn drop_elements(&self, start: usize, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let first_len = self.contiguous_available(start, n);
+        let base = self.as_mut_ptr();
+        self.slots.access(start, first_len);
+        self.slots.access(0, n - first_len);
+        self.drop_range(base.add(start), first_len);
+        self.drop_range(base, n - first_len);
+    }
+
+    /// Drops `len` contiguous elements, scrubbing them if the storage is
+    /// zeroized.
     ///
-    /// An `Option` containing the element if available, or `None` if the buffer is
-    /// empty.
+    /// # Safety
     ///
-    /// # Example
+    /// The elements must be initialized and must not be accessed afterwards.
+    unsafe fn drop_range(&self, elements: *mut T, len: usize) {
+        #[cfg(feature = "zeroize")]
+        if let Some(zeroize) = self.zeroize {
+            for i in 0..len {
+                zeroize(&mut *elements.add(i));
+            }
+        }
+        if mem::needs_drop::<T>() {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(elements, len));
+        }
+        self.wipe(elements, len);
+    }
+
+    /// Overwrites `len` contiguous slots with zero bytes, if the storage is
+    /// zeroized.
     ///
-    /// ```
-    /// use direct_ring_buffer::{create_ring_buffer};
+    /// # Safety
     ///
-    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
-    /// producer.write_slices(|data, offset| {
-    ///     data.copy_from_slice(&([3, 4, 5, 6, 7][offset..offset + data.len()]));
-    ///     data.len()
-    /// }, None);
-    /// assert_eq!(consumer.read_element(), Some(3));
-    /// assert_eq!(consumer.read_element(), Some(4));
-    /// assert_eq!(consumer.read_element(), Some(5));
-    /// assert_eq!(consumer.read_element(), Some(6));
-    /// assert_eq!(consumer.read_element(), Some(7));
-    /// assert_eq!(consumer.read_element(), None);
-    /// ```
-    pub fn read_element(&mut self) -> Option<T> where T: Copy {
-        self.buffer.read_element(&mut self.index)
-    }
-}
+    /// The slots must not contain live elements.
+    #[cfg(feature = "zeroize")]
+    unsafe fn wipe(&self, slots: *mut T, len: usize) {
+        use zeroize::Zeroize;
 
-unsafe impl<T> Send for Consumer<T> {}
+        if self.zeroize.is_some() {
+            from_raw_parts_mut(slots.cast::<MaybeUninit<T>>(), len).zeroize();
+        }
+    }
 
-struct DirectRingBuffer<T> {
-    elements: UnsafeCell<Box<[T]>>,
-    used: AtomicUsize,
-}
+    #[cfg(not(feature = "zeroize"))]
+    #[inline]
+    unsafe fn wipe(&self, _: *mut T, _: usize) {}
 
-impl<T> DirectRingBuffer<T> {
-    /// Returns the number of elements available for reading.
+    /// Returns `true` if the storage is zeroized when the elements are
+    /// released.
+    #[cfg(feature = "zeroize")]
     #[inline]
-    fn available_read(&self) -> usize {
-        self.used.load(Ordering::Acquire)
+    fn is_zeroizing(&self) -> bool {
+        self.zeroize.is_some()
     }
 
-    /// Returns the number of elements available for writing.
+    #[cfg(not(feature = "zeroize"))]
     #[inline]
-    fn available_write(&self) -> usize {
-        self.elements().len() - self.used.load(Ordering::Acquire)
+    fn is_zeroizing(&self) -> bool {
+        false
     }
 
-    /// Returns a mutable reference to the elements the buffer.
-    #[inline]
-    #[allow(clippy::mut_from_ref)]
-    fn elements(&self) -> &mut Box<[T]> {
-        unsafe { &mut *self.elements.get() }
+    /// Reads a single element from the buffer.
+    ///
+    /// An element must be available for reading. It is moved out, and its slot
+    /// is treated as vacant afterwards.
+    fn read_element(&self, index: &mut usize) -> T {
+        self.slots.access(*index, 1);
+        let element = unsafe { self.as_mut_ptr().add(*index) };
+        let ret = unsafe { ptr::read(element) };
+        // The element has been moved out.
+        unsafe { self.wipe(element, 1) };
+        self.wraparound_index(index, 1);
+        self.release(1);
+        ret
     }
 
-    /// Updates the index to wrap around the buffer.
-    #[inline]
-    fn wraparound_index(&self, index: &mut usize, advance: usize) {
-        *index = if *index + advance >= self.elements().len() {
-            0
-        } else {
-            *index + advance
+    /// Returns the readable elements starting at the index as two slices.
+    fn readable_slices(&self, index: usize) -> (&[T], &[T]) {
+        self.slices(index, self.available_read())
+    }
+
+    /// Returns the `available` elements starting at `index` as two slices.
+    fn slices(&self, index: usize, available: usize) -> (&[T], &[T]) {
+        let base = self.as_mut_ptr();
+        let first_len = self.contiguous_available(index, available);
+        self.slots.access(index, first_len);
+        self.slots.access(0, available - first_len);
+        // The elements are initialized and not written while they are readable.
+        unsafe {
+            (
+                from_raw_parts(base.add(index), first_len),
+                from_raw_parts(base, available - first_len),
+            )
         }
     }
 
-    /// Reads a single element from the buffer.
-    fn read_element(&self, index: &mut usize) -> Option<T> where T: Copy {
+    /// Returns the `available` writable elements starting at the index as two
+    /// slices.
+    #[allow(clippy::mut_from_ref)]
+    fn writable_slices(
+        &self,
+        index: usize,
+        available: usize,
+    ) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let base = self.elements.as_ptr();
+        let first_len = self.contiguous_available(index, available);
+        self.slots.access(index, first_len);
+        self.slots.access(0, available - first_len);
+        // The writable elements are accessed by the producer only.
+        unsafe {
+            (
+                from_raw_parts_mut(base.add(index), first_len),
+                from_raw_parts_mut(base, available - first_len),
+            )
+        }
+    }
+
+    /// Copies `src` to the slots starting at `index`, wrapping around the end.
+    ///
+    /// # Safety
+    ///
+    /// The `src.len()` slots starting at `index` must be writable, and `T` must
+    /// be `Copy`.
+    unsafe fn copy_from_slice(&self, index: usize, src: &[T]) {
+        let base = self.as_mut_ptr();
+        let first_len = self.contiguous_available(index, src.len());
+        self.slots.access(index, first_len);
+        self.slots.access(0, src.len() - first_len);
+        ptr::copy_nonoverlapping(src.as_ptr(), base.add(index), first_len);
+        ptr::copy_nonoverlapping(src.as_ptr().add(first_len), base, src.len() - first_len);
+    }
+
+    /// Copies the elements starting at `index` to `dst`, wrapping around the end.
+    ///
+    /// # Safety
+    ///
+    /// The `dst.len()` elements starting at `index` must be readable, and `T`
+    /// must be `Copy`.
+    unsafe fn copy_to_slice(&self, index: usize, dst: &mut [T]) {
+        let base = self.as_mut_ptr();
+        let first_len = self.contiguous_available(index, dst.len());
+        self.slots.access(index, first_len);
+        self.slots.access(0, dst.len() - first_len);
+        ptr::copy_nonoverlapping(base.add(index), dst.as_mut_ptr(), first_len);
+        ptr::copy_nonoverlapping(base, dst.as_mut_ptr().add(first_len), dst.len() - first_len);
+    }
+
+    /// Returns a reference to the element at the index without consuming it.
+    fn peek_element(&self, index: usize) -> Option<&T> {
         if self.available_read() == 0 {
             None
         } else {
-            let ret = Some(self.elements()[*index]);
-            self.wraparound_index(index, 1);
-            self.used.fetch_sub(1, Ordering::Release);
-            ret
+            self.slots.access(index, 1);
+            Some(unsafe { &*self.as_mut_ptr().add(index) })
         }
     }
 
-    /// Writes a single element to the buffer.
-    fn write_element(&self, index: &mut usize, value: T) -> bool {
-        if self.available_write() == 0 {
-            false
-        } else {
-            self.elements()[*index] = value;
-            self.wraparound_index(index, 1);
-            self.used.fetch_add(1, Ordering::Release);
-            true
-        }
+    /// Writes a single element produced by `f` to the buffer, without
+    /// publishing it.
+    ///
+    /// Space must be available for writing.
+    fn write_element(&self, index: &mut usize, f: impl FnOnce() -> T) {
+        self.slots.access(*index, 1);
+        // The slot is vacant, so it must not be dropped.
+        unsafe { ptr::write(self.as_mut_ptr().add(*index), f()) };
+        self.wraparound_index(index, 1);
     }
 
     /// Read/Write common process.
+    #[allow(clippy::too_many_arguments)]
     fn process_slices(
         &self,
         index: &mut usize,
         available: usize,
-        mut f: impl FnMut(*mut T, usize, usize) -> usize,
+        mut f: impl FnMut(*mut T, usize, usize) -> ControlFlow<usize, usize>,
         max_size: Option<usize>,
-        update_used: impl FnOnce(&AtomicUsize, usize),
+        max_chunk: usize,
+        incremental: bool,
+        update_used: impl FnMut(&Self, usize, usize),
     ) -> usize {
-        let elements = self.elements();
-        let elements_len = elements.len();
+        let base = self.as_mut_ptr();
         let mut total_processed = 0;
         let max_size = max_size.unwrap_or(available).min(available);
+        // Commits the processed elements even if the closure panics, so that
+        // the index and `used` stay consistent.
+        let mut pending = CommitGuard {
+            buffer: self,
+            update_used,
+            start: *index,
+            processed: 0,
+        };
 
         while total_processed < max_size {
             let part_start = *index;
-            let part_len = (elements_len - part_start).min(max_size - total_processed);
-            let processed = f(
-                unsafe { elements.get_unchecked_mut(part_start) },
-                part_len,
-                total_processed,
+            debug_assert!(part_start < self.capacity, "index out of bounds");
+            let part_len = self
+                .contiguous_available(part_start, max_size - total_processed)
+                .min(max_chunk);
+            self.slots.access(part_start, part_len);
+            let (processed, stop) =
+                match f(unsafe { base.add(part_start) }, part_len, total_processed) {
+                    ControlFlow::Continue(processed) => (processed, false),
+                    ControlFlow::Break(processed) => (processed, true),
+                };
+            // Trusting an oversized count would drop or expose slots that are
+            // not initialized, so this is checked in release builds as well.
+            assert!(
+                processed <= part_len,
+                "the closure returned {processed}, but the slice has only {part_len} elements"
             );
             total_processed += processed;
             self.wraparound_index(index, processed);
-            if processed < part_len {
-                // Aborting the operation because the return value
-                // from the closure is smaller then expected.
+            pending.processed += processed;
+            if incremental {
+                pending.commit();
+            }
+            if stop || processed < part_len {
+                // Aborting the operation because the closure requested it or
+                // the return value from the closure is smaller then expected.
                 break;
             }
         }
-        update_used(&self.used, total_processed);
+        pending.commit();
         total_processed
     }
 }
 
+impl<T, I: Index> Drop for DirectRingBuffer<T, I> {
+    fn drop(&mut self) {
+        let used = self.available_read();
+        let read_index = self.read_index.load(Ordering::Relaxed);
+        unsafe { self.drop_elements(read_index, used) };
+        let capacity = self.capacity;
+        // No live elements remain.
+        unsafe { self.wipe(self.as_mut_ptr(), capacity) };
+        self.free_elements();
+    }
+}
+
+/// Guard that commits the processed elements to `used` when dropped.
+struct CommitGuard<'a, T, I: Index, F: FnMut(&DirectRingBuffer<T, I>, usize, usize)> {
+    buffer: &'a DirectRingBuffer<T, I>,
+    update_used: F,
+    /// Index of the first element processed since the last commit.
+    start: usize,
+    processed: usize,
+}
+
+impl<T, I: Index, F: FnMut(&DirectRingBuffer<T, I>, usize, usize)> CommitGuard<'_, T, I, F> {
+    /// Commits the elements processed since the last commit.
+    #[inline]
+    fn commit(&mut self) {
+        if self.processed != 0 {
+            (self.update_used)(self.buffer, self.start, self.processed);
+            self.start = (self.start + self.processed) % self.buffer.capacity();
+            self.processed = 0;
+        }
+    }
+}
+
+impl<T, I: Index, F: FnMut(&DirectRingBuffer<T, I>, usize, usize)> Drop
+    for CommitGuard<'_, T, I, F>
+{
+    fn drop(&mut self) {
+        self.commit();
+    }
+}
+
 /// Creates a ring buffer with the specified size.
 ///
 /// # Arguments
@@ -451,21 +4186,311 @@ impl<T> DirectRingBuffer<T> {
 /// }, None);
 /// assert_eq!(read_data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
 /// ```
-#[allow(clippy::uninit_vec)]
-pub fn create_ring_buffer<T: Copy>(size: usize) -> (Producer<T>, Consumer<T>) {
-    let buffer = Arc::new(DirectRingBuffer {
-        elements: UnsafeCell::new({
-            let mut vec = Vec::<T>::with_capacity(size);
-            unsafe { vec.set_len(size) };
-            vec.into_boxed_slice()
-        }),
-        used: AtomicUsize::new(0),
-    });
+pub fn create_ring_buffer<T>(size: usize) -> (Producer<T>, Consumer<T>) {
+    RingBufferBuilder::new().capacity(size).build()
+}
+
+/// Creates a ring buffer with the specified size, whose counters shared by the
+/// producer and the consumer are of the index type `I`.
+///
+/// This function behaves like [`create_ring_buffer`], but the capacity is
+/// limited to [`Index::MAX_CAPACITY`]. See [`RingBufferBuilder::index`].
+///
+/// # Arguments
+///
+/// * `size` - The size of the ring buffer.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T, I>` and a `Consumer<T, I>`.
+///
+/// # Panics
+///
+/// Panics if `size` exceeds the maximum capacity of the index type.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_with_index;
+///
+/// let (mut producer, mut consumer) = create_ring_buffer_with_index::<u8, u16>(256);
+/// assert_eq!(producer.capacity(), 256);
+/// assert!(producer.write_element(1));
+/// assert_eq!(consumer.read_element(), Some(1));
+/// ```
+pub fn create_ring_buffer_with_index<T, I: Index>(size: usize) -> (Producer<T, I>, Consumer<T, I>) {
+    RingBufferBuilder::new().index::<I>().capacity(size).build()
+}
+
+/// Creates a ring buffer with the specified size, whose storage is filled
+/// with zero bytes.
+///
+/// This function behaves like [`create_ring_buffer`], but the recycled heap
+/// contents are never observable through the writable slices, and the zeroed
+/// storage is treated as initialized elements, so that it is not filled again
+/// before it is handed out for the first time.
+///
+/// # Arguments
+///
+/// * `size` - The size of the ring buffer.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T>` and a `Consumer<T>`.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_zeroed;
+///
+/// let (mut producer, _consumer) = create_ring_buffer_zeroed::<f32>(4);
+/// let (first, _) = producer.vacant_slices_uninit();
+/// assert!(first.iter().all(|e| *unsafe { e.assume_init_ref() } == 0.0));
+/// ```
+pub fn create_ring_buffer_zeroed<T: Zeroable>(size: usize) -> (Producer<T>, Consumer<T>) {
+    let (mut producer, consumer) = RingBufferBuilder::new().capacity(size).zeroed().build();
+    producer.initialized = size;
+    (producer, consumer)
+}
+
+/// Creates a ring buffer with the specified size, whose storage is filled
+/// with default values.
+///
+/// This function behaves like [`create_ring_buffer`], but every element of the
+/// storage is initialized with `T::default()` up front, instead of when it is
+/// handed out for the first time. The default values are not readable and are
+/// overwritten without being dropped, so `T` must be `Copy`, which rules out
+/// defaults that own resources.
+///
+/// # Arguments
+///
+/// * `size` - The size of the ring buffer.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T>` and a `Consumer<T>`.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_default;
+///
+/// let (mut producer, _consumer) = create_ring_buffer_default::<Option<u8>>(4);
+/// let (first, _) = producer.vacant_slices_uninit();
+/// assert!(first.iter().all(|e| unsafe { e.assume_init_ref() }.is_none()));
+/// ```
+///
+/// Defaults that would leak when overwritten are rejected:
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer_default;
+/// use std::rc::Rc;
+///
+/// let (mut producer, _consumer) = create_ring_buffer_default::<Rc<()>>(4);
+/// producer.write_element(Rc::new(()));
+/// ```
+pub fn create_ring_buffer_default<T: Copy + Default>(size: usize) -> (Producer<T>, Consumer<T>) {
+    let (mut producer, consumer) = create_ring_buffer(size);
+    let (first, _) = producer.vacant_slices_uninit();
+    for element in first {
+        element.write(T::default());
+    }
+    producer.initialized = size;
+    (producer, consumer)
+}
+
+/// Creates a ring buffer with the specified size, reporting failures as errors.
+///
+/// This function behaves like [`create_ring_buffer`], but returns an error
+/// instead of creating a useless buffer of zero capacity or aborting when the
+/// storage cannot be allocated.
+///
+/// # Arguments
+///
+/// * `size` - The size of the ring buffer.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple of a `Producer<T>` and a `Consumer<T>`, or a
+/// [`CreateError`] describing why the ring buffer could not be created.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::{try_create_ring_buffer, CreateError};
+///
+/// let (producer, _consumer) = try_create_ring_buffer::<u8>(10).unwrap();
+/// assert_eq!(producer.capacity(), 10);
+/// assert_eq!(try_create_ring_buffer::<u8>(0).unwrap_err(), CreateError::ZeroCapacity);
+/// assert_eq!(
+///     try_create_ring_buffer::<u32>(usize::MAX).unwrap_err(),
+///     CreateError::CapacityOverflow
+/// );
+/// ```
+pub fn try_create_ring_buffer<T>(size: usize) -> Result<(Producer<T>, Consumer<T>), CreateError> {
+    RingBufferBuilder::new().capacity(size).try_build()
+}
+
+/// Creates a ring buffer whose initial contents are the elements of a `Vec`.
+///
+/// The capacity of the ring buffer is the length of `vec`, so the ring buffer
+/// starts full and the elements are immediately readable. Use
+/// [`create_ring_buffer_from_with_capacity`] to leave room for writing.
+///
+/// # Arguments
+///
+/// * `vec` - The initial contents of the ring buffer.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T>` and a `Consumer<T>`.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_from;
+///
+/// let (producer, mut consumer) = create_ring_buffer_from(vec![0u8; 4]);
+/// assert!(producer.is_full());
+/// assert_eq!(consumer.read_array::<4>(), Some([0; 4]));
+/// ```
+pub fn create_ring_buffer_from<T>(vec: Vec<T>) -> (Producer<T>, Consumer<T>) {
+    let capacity = vec.len();
+    create_ring_buffer_from_with_capacity(vec, capacity)
+}
+
+/// Creates a ring buffer with the specified capacity whose initial contents are
+/// the elements of a `Vec`.
+///
+/// The elements of `vec` are immediately readable, and the remaining
+/// `capacity - vec.len()` elements are available for writing.
+///
+/// # Arguments
+///
+/// * `vec` - The initial contents of the ring buffer.
+/// * `capacity` - The capacity of the ring buffer.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T>` and a `Consumer<T>`.
+///
+/// # Panics
+///
+/// Panics if `capacity` is less than the length of `vec`.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_from_with_capacity;
+///
+/// // Primes the buffer with two elements of preroll silence.
+/// let (mut producer, mut consumer) = create_ring_buffer_from_with_capacity(vec![0i16; 2], 5);
+/// assert_eq!(producer.available(), 3);
+/// producer.write_exact(&[1, 2, 3]);
+/// assert_eq!(consumer.read_array::<5>(), Some([0, 0, 1, 2, 3]));
+/// ```
+pub fn create_ring_buffer_from_with_capacity<T>(
+    vec: Vec<T>,
+    capacity: usize,
+) -> (Producer<T>, Consumer<T>) {
+    let used = vec.len();
+    assert!(
+        capacity >= used,
+        "capacity is less than the length of the vec"
+    );
+    let mut vec = ManuallyDrop::new(vec);
+    // `MaybeUninit<T>` has the same layout as `T`.
+    let mut vec = unsafe {
+        Vec::from_raw_parts(
+            vec.as_mut_ptr().cast::<MaybeUninit<T>>(),
+            used,
+            vec.capacity(),
+        )
+    };
+    vec.resize_with(capacity, MaybeUninit::uninit);
+    split_ring_buffer(DirectRingBuffer::new(vec.into_boxed_slice(), used))
+}
+
+/// Creates a ring buffer with the specified capacity whose initial contents are
+/// taken from an iterator.
+///
+/// Up to `capacity` items are taken from `iter` and are immediately readable.
+/// The items are stored directly in the ring buffer without an intermediate
+/// allocation.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity of the ring buffer.
+/// * `iter` - The iterator providing the initial contents.
+///
+/// # Returns
+///
+/// A tuple containing a `Producer<T>`, a `Consumer<T>` and the number of items
+/// taken from the iterator.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer_from_iter;
+///
+/// let (producer, mut consumer, taken) = create_ring_buffer_from_iter(4, 1u8..);
+/// assert_eq!(taken, 4);
+/// assert!(producer.is_full());
+/// assert_eq!(consumer.read_array::<4>(), Some([1, 2, 3, 4]));
+/// ```
+pub fn create_ring_buffer_from_iter<T>(
+    capacity: usize,
+    iter: impl IntoIterator<Item = T>,
+) -> (Producer<T>, Consumer<T>, usize) {
+    let mut vec = Vec::with_capacity(capacity);
+    vec.extend(iter.into_iter().take(capacity));
+    let taken = vec.len();
+    let (producer, consumer) = create_ring_buffer_from_with_capacity(vec, capacity);
+    (producer, consumer, taken)
+}
+
+/// Creates the producer and the consumer sharing the buffer, whose readable
+/// elements start at the beginning of the storage.
+fn split_ring_buffer<T, I: Index>(
+    buffer: DirectRingBuffer<T, I>,
+) -> (Producer<T, I>, Consumer<T, I>) {
+    let used = buffer.available_read();
+    let index = if used == buffer.capacity() { 0 } else { used };
+    let read_pos = buffer.read_pos.load(Ordering::Relaxed);
+    let write_pos = buffer.write_pos.load(Ordering::Relaxed);
+    let buffer = Arc::new(buffer);
     (
         Producer {
             buffer: Arc::clone(&buffer),
+            index,
+            written: used as u64,
+            initialized: used,
+            prepared: used as u64,
+            cached_read_pos: read_pos,
+        },
+        Consumer {
+            buffer,
             index: 0,
+            read: 0,
+            cached_write_pos: write_pos,
         },
-        Consumer { buffer, index: 0 },
     )
 }
+
+/// Returns the size of a memory page.
+#[cfg(all(any(feature = "numa", feature = "double-mapped"), target_os = "linux"))]
+fn page_size() -> usize {
+    match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as usize,
+        _ => 4096,
+    }
+}
+
+/// Reinterprets a slice of initialized elements as a slice of `T`.
+///
+/// # Safety
+///
+/// All elements of `slice` must be initialized.
+unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
+}
@@ -0,0 +1,88 @@
+use core::ptr::{self, NonNull};
+
+use crate::page_size;
+
+/// Maps `size` bytes of zeroed memory twice back-to-back, so that the bytes
+/// at `offset` and `offset + size` are the same for any `offset` below `size`.
+///
+/// `size` must be a non-zero multiple of the page size, and `align` a power of
+/// two. The returned mapping is aligned to `align` and to the page size, and
+/// must be freed with [`unmap`].
+pub(crate) fn map(size: usize, align: usize) -> Option<NonNull<u8>> {
+    let page = page_size();
+    let align = align.max(page);
+    let len = size.checked_mul(2)?;
+    let reserved = len.checked_add(align - page)?;
+    unsafe {
+        let fd = libc::memfd_create(c"direct_ring_buffer".as_ptr(), libc::MFD_CLOEXEC);
+        if fd < 0 {
+            return None;
+        }
+        let base = map_file(fd, size, reserved, align);
+        // The mappings keep the file alive.
+        libc::close(fd);
+        base
+    }
+}
+
+/// Maps the file twice into an aligned range of `reserved` bytes.
+unsafe fn map_file(
+    fd: libc::c_int,
+    size: usize,
+    reserved: usize,
+    align: usize,
+) -> Option<NonNull<u8>> {
+    let len = size * 2;
+    if libc::ftruncate(fd, size as libc::off_t) != 0 {
+        return None;
+    }
+    // Reserves the address range first, so that the two views can be placed
+    // next to each other.
+    let reservation = libc::mmap(
+        ptr::null_mut(),
+        reserved,
+        libc::PROT_NONE,
+        libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_NORESERVE,
+        -1,
+        0,
+    );
+    if reservation == libc::MAP_FAILED {
+        return None;
+    }
+    // Trims the reservation to the aligned range.
+    let start = reservation as usize;
+    let base = (start + align - 1) & !(align - 1);
+    if base != start {
+        libc::munmap(reservation, base - start);
+    }
+    if start + reserved != base + len {
+        libc::munmap(
+            (base + len) as *mut libc::c_void,
+            start + reserved - base - len,
+        );
+    }
+    for offset in [0, size] {
+        let view = libc::mmap(
+            (base + offset) as *mut libc::c_void,
+            size,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED | libc::MAP_FIXED,
+            fd,
+            0,
+        );
+        if view == libc::MAP_FAILED {
+            libc::munmap(base as *mut libc::c_void, len);
+            return None;
+        }
+    }
+    NonNull::new(base as *mut u8)
+}
+
+/// Unmaps a mapping of `size` bytes returned by [`map`].
+///
+/// # Safety
+///
+/// The mapping must not be accessed afterwards.
+pub(crate) unsafe fn unmap(base: NonNull<u8>, size: usize) {
+    libc::munmap(base.as_ptr().cast(), size * 2);
+}
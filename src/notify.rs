@@ -0,0 +1,311 @@
+use core::{fmt, sync::atomic::fence};
+use std::{
+    io,
+    os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+    sync::OnceLock,
+};
+
+use mio::{event::Source, unix::SourceFd, Interest, Registry, Token};
+
+use crate::{
+    sync::{Arc, AtomicBool, Ordering},
+    Consumer, DirectRingBuffer, Index, Producer,
+};
+
+/// File descriptors through which a slot signals readiness.
+///
+/// On Linux, a single eventfd is both written and read. Elsewhere, a pipe is
+/// used, and `write` is its write end.
+struct Fds {
+    read: OwnedFd,
+    write: Option<OwnedFd>,
+}
+
+impl Fds {
+    #[cfg(target_os = "linux")]
+    fn new() -> io::Result<Self> {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            read: unsafe { OwnedFd::from_raw_fd(fd) },
+            write: None,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn new() -> io::Result<Self> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        for fd in fds {
+            unsafe {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                if flags < 0
+                    || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) != 0
+                    || libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) != 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+        }
+        Ok(Self {
+            read,
+            write: Some(write),
+        })
+    }
+
+    /// Makes the read end readable.
+    fn signal(&self) {
+        let fd = self.write.as_ref().unwrap_or(&self.read).as_raw_fd();
+        let value = 1u64;
+        // Fails only if the counter or the pipe is full, and then readable
+        // already.
+        unsafe { libc::write(fd, (&value as *const u64).cast(), 8) };
+    }
+
+    /// Consumes the pending signals.
+    fn clear(&self) {
+        let mut buf = [0u8; 64];
+        loop {
+            let read =
+                unsafe { libc::read(self.read.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+            if read < buf.len() as isize {
+                break;
+            }
+        }
+    }
+}
+
+/// Slot through which a handle is notified by file descriptor when the other
+/// handle makes progress.
+///
+/// The protocol is the one of [`WaitSlot`](crate::blocking::WaitSlot): the
+/// notified side sets `waiting` and places a sequentially consistent fence
+/// before checking its condition again, and the other side checks `waiting`
+/// with a sequentially consistent load after storing a position with
+/// [`PROGRESS`](crate::sync::PROGRESS) ordering. The file descriptors are
+/// created by the first call to [`Producer::notifier`] or
+/// [`Consumer::notifier`].
+#[derive(Default)]
+pub(crate) struct NotifySlot {
+    waiting: AtomicBool,
+    fds: OnceLock<Fds>,
+}
+
+impl NotifySlot {
+    /// Returns the file descriptors of the slot, creating them if needed.
+    fn fds(&self) -> io::Result<&Fds> {
+        if let Some(fds) = self.fds.get() {
+            return Ok(fds);
+        }
+        let fds = Fds::new()?;
+        Ok(self.fds.get_or_init(|| fds))
+    }
+
+    /// Signals the file descriptor if a notifier is armed.
+    #[inline]
+    pub(crate) fn notify(&self) {
+        if self.waiting.load(Ordering::SeqCst) && self.waiting.swap(false, Ordering::Relaxed) {
+            if let Some(fds) = self.fds.get() {
+                fds.signal();
+            }
+        }
+    }
+}
+
+/// Notifier making a file descriptor readable when the other handle makes
+/// progress.
+///
+/// This struct is created by [`Producer::notifier`] and
+/// [`Consumer::notifier`]. It lets an event loop based on
+/// [`mio`](https://docs.rs/mio), or on `epoll` or `kqueue` through
+/// [`AsRawFd`], wait for the ring buffer alongside sockets and other sources of
+/// events. The file descriptor is an eventfd on Linux and a pipe on the other
+/// Unix systems.
+///
+/// Like [`Waiter`](crate::Waiter), the notifier signals only the first
+/// progress after [`Notifier::arm`] has been called: the transition from full
+/// to not full for the producer, and from empty to not empty for the consumer.
+/// The loop arms the notifier, processes the ring buffer until it cannot make
+/// progress, and waits for the file descriptor only then. The file descriptor
+/// is also signaled when the other handle is dropped.
+///
+/// This struct is available only with the `mio` feature, on Unix.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let (mut producer, mut consumer) = create_ring_buffer::<u8>(4);
+/// let mut notifier = consumer.notifier().unwrap();
+/// let mut poll = Poll::new().unwrap();
+/// poll.registry()
+///     .register(&mut notifier, Token(0), Interest::READABLE)
+///     .unwrap();
+/// let writer = std::thread::spawn(move || {
+///     for i in 0..10 {
+///         while !producer.write_element(i) {
+///             std::thread::yield_now();
+///         }
+///     }
+/// });
+/// let mut events = Events::with_capacity(4);
+/// let mut received = Vec::new();
+/// while received.len() < 10 {
+///     notifier.arm();
+///     let mut progress = false;
+///     while let Some(value) = consumer.read_element() {
+///         received.push(value);
+///         progress = true;
+///     }
+///     if !progress {
+///         poll.poll(&mut events, None).unwrap();
+///     }
+/// }
+/// writer.join().unwrap();
+/// assert_eq!(received, (0..10).collect::<Vec<_>>());
+/// ```
+///
+/// The notifier can be sent to another thread only if the elements can be
+/// sent, since it may drop them there as the last owner of the ring buffer.
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (_producer, consumer) = create_ring_buffer::<std::rc::Rc<u8>>(1);
+/// let notifier = consumer.notifier().unwrap();
+/// std::thread::spawn(move || drop(notifier));
+/// ```
+pub struct Notifier<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
+    /// `true` if the notifier waits for space, `false` for elements.
+    space: bool,
+}
+
+impl<T, I: Index> Notifier<T, I> {
+    fn new(buffer: &Arc<DirectRingBuffer<T, I>>, space: bool) -> io::Result<Self> {
+        let notifier = Self {
+            buffer: Arc::clone(buffer),
+            space,
+        };
+        notifier.slot().fds()?;
+        Ok(notifier)
+    }
+
+    fn slot(&self) -> &NotifySlot {
+        if self.space {
+            &self.buffer.space_notify
+        } else {
+            &self.buffer.data_notify
+        }
+    }
+
+    fn fds(&self) -> &Fds {
+        self.slot()
+            .fds
+            .get()
+            .expect("the file descriptors are created with the notifier")
+    }
+
+    /// Consumes the pending signal, and arms the notifier so that the file
+    /// descriptor is signaled the next time the other handle makes progress or
+    /// is dropped.
+    pub fn arm(&self) {
+        let slot = self.slot();
+        self.fds().clear();
+        slot.waiting.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+    }
+}
+
+impl<T, I: Index> AsFd for Notifier<T, I> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fds().read.as_fd()
+    }
+}
+
+impl<T, I: Index> AsRawFd for Notifier<T, I> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fds().read.as_raw_fd()
+    }
+}
+
+/// Registers the file descriptor for [`Interest::READABLE`].
+impl<T, I: Index> Source for Notifier<T, I> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl<T, I: Index> fmt::Debug for Notifier<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Notifier")
+            .field("space", &self.space)
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+// The notifier only accesses the elements if it is the last owner of the ring
+// buffer, and then drops the unread ones, as an `Arc` of them would.
+unsafe impl<T: Send, I: Index> Send for Notifier<T, I> {}
+unsafe impl<T: Send + Sync, I: Index> Sync for Notifier<T, I> {}
+
+impl<T, I: Index> Producer<T, I> {
+    /// Creates a notifier signaling a file descriptor when space is freed.
+    ///
+    /// See [`Notifier`] for details. The notifier keeps the ring buffer alive,
+    /// so [`Producer::reunite`] fails while it exists.
+    ///
+    /// This method is available only with the `mio` feature, on Unix.
+    ///
+    /// # Returns
+    ///
+    /// The notifier, or the error of the operating system if the file
+    /// descriptor cannot be created.
+    pub fn notifier(&self) -> io::Result<Notifier<T, I>> {
+        Notifier::new(&self.buffer, true)
+    }
+}
+
+impl<T, I: Index> Consumer<T, I> {
+    /// Creates a notifier signaling a file descriptor when elements are
+    /// written.
+    ///
+    /// See [`Notifier`] for details. The notifier keeps the ring buffer alive,
+    /// so [`Producer::reunite`] fails while it exists.
+    ///
+    /// This method is available only with the `mio` feature, on Unix.
+    ///
+    /// # Returns
+    ///
+    /// The notifier, or the error of the operating system if the file
+    /// descriptor cannot be created.
+    pub fn notifier(&self) -> io::Result<Notifier<T, I>> {
+        Notifier::new(&self.buffer, false)
+    }
+}
@@ -0,0 +1,189 @@
+use core::{fmt, ops::ControlFlow, slice::from_raw_parts};
+
+use crate::{
+    sync::{Arc, Ordering, PROGRESS},
+    DirectRingBuffer, Index,
+};
+
+/// Read-only cursor over the elements written to the ring buffer.
+///
+/// This struct is created by [`Producer::observer`](crate::Producer::observer).
+/// It reads the elements with its own cursor, independently of the consumer,
+/// and never frees space in the ring buffer. The producer does not overwrite
+/// elements the observer has not read yet, so the observer should keep up with
+/// the producer. The observer can be sent to another thread only if the
+/// elements can be both sent and shared, since the consumer may access the same
+/// elements at the same time. It can be shared between threads if the elements
+/// can be shared.
+///
+/// ```compile_fail
+/// use direct_ring_buffer::create_ring_buffer;
+///
+/// let (mut producer, _consumer) = create_ring_buffer::<*const u8>(1);
+/// let observer = producer.observer().unwrap();
+/// std::thread::spawn(move || drop(observer));
+/// ```
+pub struct Observer<T, I: Index = usize> {
+    buffer: Arc<DirectRingBuffer<T, I>>,
+    index: usize,
+}
+
+impl<T, I: Index> Observer<T, I> {
+    /// Attaches an observer starting at the producer's current `index`.
+    pub(crate) fn attach(buffer: &Arc<DirectRingBuffer<T, I>>, index: usize) -> Option<Self> {
+        if buffer.observer_attached.load(Ordering::Acquire) {
+            return None;
+        }
+        let write_pos = buffer.write_pos.load(Ordering::Relaxed);
+        buffer.observer_pos.store(write_pos, Ordering::Relaxed);
+        buffer.observer_attached.store(true, Ordering::Release);
+        Some(Self {
+            buffer: Arc::clone(buffer),
+            index,
+        })
+    }
+
+    /// Returns the capacity of the ring buffer.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns the number of elements available for observing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, _consumer) = create_ring_buffer::<u8>(5);
+    /// producer.write_element(1);
+    /// let observer = producer.observer().unwrap();
+    /// producer.write_element(2);
+    /// assert_eq!(observer.available(), 1);
+    /// ```
+    pub fn available(&self) -> usize {
+        self.buffer.available_observe()
+    }
+
+    /// Returns the observable elements as two slices.
+    ///
+    /// The second slice is non-empty only if the elements wrap around the end
+    /// of the ring buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.buffer.slices(self.index, self.available())
+    }
+
+    /// Peeks at the next observable element without advancing the cursor.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a reference to the element, or `None` if no
+    /// element is available.
+    pub fn peek_element(&self) -> Option<&T> {
+        self.as_slices().0.first()
+    }
+
+    /// Reads elements through the observer's cursor.
+    ///
+    /// This method behaves like [`Consumer::read_slices`](crate::Consumer::read_slices),
+    /// but the processed elements remain readable by the consumer.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that processes the observed elements. It receives a
+    ///   slice and the offset from the start of the operation, and returns the
+    ///   number of elements processed.
+    /// * `max_size` - An optional maximum number of elements to observe. If
+    ///   `None`, all available elements are observed.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements observed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::{create_ring_buffer};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(5);
+    /// let mut observer = producer.observer().unwrap();
+    /// producer.write_exact(&[1, 2, 3]);
+    /// let mut peak = 0;
+    /// observer.read_slices(|data, _| {
+    ///     peak = data.iter().copied().fold(peak, u8::max);
+    ///     data.len()
+    /// }, None);
+    /// assert_eq!(peak, 3);
+    /// assert_eq!(consumer.available(), 3);
+    /// ```
+    pub fn read_slices(
+        &mut self,
+        mut f: impl FnMut(&[T], usize) -> usize,
+        max_size: Option<usize>,
+    ) -> usize {
+        let available = self.available();
+        self.buffer.process_slices(
+            &mut self.index,
+            available,
+            |buf, len, process_offset| {
+                ControlFlow::Continue(f(
+                    // No boundaries are crossed.
+                    unsafe { from_raw_parts(buf, len) },
+                    process_offset,
+                ))
+            },
+            max_size,
+            usize::MAX,
+            false,
+            |buffer, _, processed| buffer.release_observed(processed),
+        )
+    }
+
+    /// Advances the cursor by up to `n` elements without reading them.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements skipped.
+    pub fn advance(&mut self, n: usize) -> usize {
+        self.read_slices(|data, _| data.len(), Some(n))
+    }
+}
+
+impl<T: Copy, I: Index> Observer<T, I> {
+    /// Reads a copy of the next observable element.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the element, or `None` if no element is
+    /// available.
+    pub fn read_element(&mut self) -> Option<T> {
+        let element = self.peek_element().copied();
+        if element.is_some() {
+            self.advance(1);
+        }
+        element
+    }
+}
+
+impl<T, I: Index> Drop for Observer<T, I> {
+    fn drop(&mut self) {
+        self.buffer
+            .observer_attached
+            .store(false, PROGRESS);
+        // The space held by the observer is freed.
+        self.buffer.wake_producer();
+    }
+}
+
+unsafe impl<T: Send + Sync, I: Index> Send for Observer<T, I> {}
+unsafe impl<T: Sync, I: Index> Sync for Observer<T, I> {}
+
+impl<T, I: Index> fmt::Debug for Observer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer")
+            .field("capacity", &self.capacity())
+            .field("index", &self.index)
+            .field("available", &self.available())
+            .finish()
+    }
+}
@@ -0,0 +1,185 @@
+use core::{fmt, mem::MaybeUninit, ptr};
+
+use crate::{distance, sync::Ordering, Consumer, Index};
+
+/// Consumer of a ring buffer whose producer overwrites the oldest elements.
+///
+/// This struct is created by
+/// [`RingBufferBuilder::build_overwriting`](crate::RingBufferBuilder::build_overwriting).
+/// The producer may evict an element while it is being read, so each element
+/// is copied out first and kept only if the read position has not moved in
+/// the meantime, in the manner of a seqlock. The elements evicted before they
+/// could be read are counted by [`OverwriteConsumer::dropped`].
+///
+/// The read position wraps around at the range of the [`Index`] type, so an
+/// element copied while the producer evicts a whole multiple of that range
+/// passes the check. With a narrow index type, the consumer should not be
+/// preempted for that long.
+pub struct OverwriteConsumer<T, I: Index = usize> {
+    consumer: Consumer<T, I>,
+    /// Read position the index of the consumer corresponds to.
+    pos: usize,
+    /// Number of elements evicted before they were read.
+    dropped: u64,
+}
+
+impl<T, I: Index> OverwriteConsumer<T, I> {
+    /// Wraps the consumer of an overwriting ring buffer.
+    pub(crate) fn new(consumer: Consumer<T, I>) -> Self {
+        let pos = consumer.buffer.read_pos.load(Ordering::Relaxed);
+        Self {
+            consumer,
+            pos,
+            dropped: 0,
+        }
+    }
+
+    /// Returns the capacity of the ring buffer.
+    pub fn capacity(&self) -> usize {
+        self.consumer.capacity()
+    }
+
+    /// Returns the number of elements available for reading.
+    ///
+    /// The producer may evict some of them before they are read.
+    pub fn available(&self) -> usize {
+        self.consumer.available()
+    }
+
+    /// Returns `true` if no elements are available for reading.
+    pub fn is_empty(&self) -> bool {
+        self.available() == 0
+    }
+
+    /// Returns the total number of elements read.
+    pub fn total_read(&self) -> u64 {
+        self.consumer.read
+    }
+
+    /// Returns the total number of elements evicted by the producer before
+    /// they were read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(2)
+    ///     .build_overwriting();
+    /// producer.write_element(1);
+    /// producer.write_element(2);
+    /// producer.write_element(3);
+    /// assert_eq!(consumer.read_element(), Some(2));
+    /// assert_eq!(consumer.dropped(), 1);
+    /// ```
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+impl<T: Copy, I: Index> OverwriteConsumer<T, I> {
+    /// Reads the oldest element that has not been evicted.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the element, or `None` if the buffer is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(2)
+    ///     .build_overwriting();
+    /// assert_eq!(consumer.read_element(), None);
+    /// producer.write_from_slice(&[1, 2, 3]);
+    /// assert_eq!(consumer.read_element(), Some(2));
+    /// assert_eq!(consumer.read_element(), Some(3));
+    /// assert_eq!(consumer.read_element(), None);
+    /// ```
+    pub fn read_element(&mut self) -> Option<T> {
+        let buffer = &self.consumer.buffer;
+        loop {
+            let read_pos = buffer.read_pos.load(Ordering::Acquire);
+            if read_pos != self.pos {
+                // The producer has evicted the elements in between.
+                let evicted = distance::<I>(self.pos, read_pos);
+                buffer.wraparound_index(&mut self.consumer.index, evicted % buffer.capacity());
+                self.dropped += evicted as u64;
+                self.pos = read_pos;
+            }
+            let write_pos = buffer.write_pos.load(Ordering::Acquire);
+            if read_pos == write_pos {
+                return None;
+            }
+            buffer.slots.access(self.consumer.index, 1);
+            // The slot may be overwritten while it is copied, so the copy is
+            // not a valid `T` until the read position has been advanced.
+            let element = unsafe {
+                ptr::read_volatile(
+                    buffer
+                        .as_mut_ptr()
+                        .add(self.consumer.index)
+                        .cast::<MaybeUninit<T>>(),
+                )
+            };
+            if buffer.read_pos.advance_from(read_pos, 1).is_ok() {
+                buffer.wraparound_index(&mut self.consumer.index, 1);
+                self.pos = read_pos.wrapping_add(1);
+                self.consumer.read += 1;
+                buffer.wake_producer();
+                // The producer evicts an element before overwriting it.
+                return Some(unsafe { element.assume_init() });
+            }
+        }
+    }
+
+    /// Reads the oldest elements that have not been evicted into a slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The slice to read the elements into.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements read, which is less than the length of `dst` if
+    /// the buffer runs empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::RingBufferBuilder;
+    ///
+    /// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+    ///     .capacity(3)
+    ///     .build_overwriting();
+    /// producer.write_from_slice(&[1, 2, 3, 4]);
+    /// let mut dst = [0; 4];
+    /// assert_eq!(consumer.read_into_slice(&mut dst), 3);
+    /// assert_eq!(&dst[..3], &[2, 3, 4]);
+    /// ```
+    pub fn read_into_slice(&mut self, dst: &mut [T]) -> usize {
+        // Each element is validated on its own, so that no element that was
+        // overwritten while being copied is left in `dst`.
+        for (len, slot) in dst.iter_mut().enumerate() {
+            match self.read_element() {
+                Some(element) => *slot = element,
+                None => return len,
+            }
+        }
+        dst.len()
+    }
+}
+
+impl<T, I: Index> fmt::Debug for OverwriteConsumer<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverwriteConsumer")
+            .field("capacity", &self.capacity())
+            .field("index", &self.consumer.index)
+            .field("available", &self.available())
+            .field("dropped", &self.dropped)
+            .finish()
+    }
+}
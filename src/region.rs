@@ -0,0 +1,14 @@
+/// Contiguous region of the ring buffer storage.
+///
+/// A region is described by an offset from the base pointer of the storage and
+/// a number of elements, so that it can be handed to code outside of Rust, such
+/// as C audio drivers or DMA controllers. It is returned by
+/// [`Producer::writable_regions`](crate::Producer::writable_regions) and
+/// [`Consumer::readable_regions`](crate::Consumer::readable_regions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Region {
+    /// Offset of the first element from the base pointer, in elements.
+    pub offset: usize,
+    /// Number of elements in the region.
+    pub len: usize,
+}
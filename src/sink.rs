@@ -0,0 +1,155 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_sink::Sink;
+
+use crate::{AsyncProducer, Closed, Index, Producer};
+
+/// Sink writing to the ring buffer.
+///
+/// This struct is created by [`Producer::into_sink`]. It implements
+/// [`Sink<T>`] for single elements, which is ready once there is space for an
+/// element, and [`Sink<Vec<T>>`] for batches, which is ready once the previous
+/// batch has been written. A batch that does not fit is kept by the sink and
+/// written as space is freed, while the sink is flushed or becomes ready again.
+///
+/// Both return [`Closed`] once the consumer has been dropped.
+///
+/// This struct is available only with the `futures` feature.
+#[derive(Debug)]
+pub struct ProducerSink<T, I: Index = usize> {
+    producer: AsyncProducer<T, I>,
+    /// Elements accepted by the sink but not yet written.
+    pending: VecDeque<T>,
+}
+
+impl<T, I: Index> ProducerSink<T, I> {
+    /// Unwraps the producer.
+    ///
+    /// The elements accepted by the sink but not yet written are dropped.
+    pub fn into_inner(self) -> Producer<T, I> {
+        self.producer.into_inner()
+    }
+
+    /// Writes as many pending elements as fit into the ring buffer.
+    fn write_pending(&mut self) {
+        let producer = self.producer.get_mut();
+        let len = producer.available().min(self.pending.len());
+        if len != 0 {
+            let mut batch = producer.batch();
+            for value in self.pending.drain(..len) {
+                batch.write_element(value);
+            }
+        }
+    }
+
+    /// Writes the pending elements, waiting for space as needed.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        while !self.pending.is_empty() {
+            match self.producer.poll_writable(cx) {
+                Poll::Ready(true) => self.write_pending(),
+                Poll::Ready(false) => return Poll::Ready(Err(Closed)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+// The elements are never pinned.
+impl<T, I: Index> Unpin for ProducerSink<T, I> {}
+
+impl<T, I: Index> Sink<T> for ProducerSink<T, I> {
+    type Error = Closed;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        let this = self.get_mut();
+        match this.poll_pending(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        this.producer
+            .poll_writable(cx)
+            .map(|writable| if writable { Ok(()) } else { Err(Closed) })
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Closed> {
+        let this = self.get_mut();
+        if this.pending.is_empty() {
+            if let Err(item) = this.producer.get_mut().try_write_element(item) {
+                // Called without waiting for the sink to be ready.
+                this.pending.push_back(item);
+            }
+        } else {
+            this.pending.push_back(item);
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().poll_pending(cx)
+    }
+}
+
+impl<T, I: Index> Sink<Vec<T>> for ProducerSink<T, I> {
+    type Error = Closed;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Vec<T>) -> Result<(), Closed> {
+        let this = self.get_mut();
+        if this.pending.is_empty() {
+            this.pending = item.into();
+        } else {
+            this.pending.extend(item);
+        }
+        this.write_pending();
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().poll_pending(cx)
+    }
+}
+
+impl<T, I: Index> Producer<T, I> {
+    /// Converts the producer into a sink of elements and of batches of them.
+    ///
+    /// See [`ProducerSink`] for details.
+    ///
+    /// This method is available only with the `futures` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::{executor::block_on, SinkExt};
+    ///
+    /// let (producer, mut consumer) = create_ring_buffer::<u8>(4);
+    /// let mut sink = producer.into_sink();
+    /// block_on(async {
+    ///     sink.send(1).await.unwrap();
+    ///     sink.send(vec![2, 3]).await.unwrap();
+    /// });
+    /// assert_eq!(consumer.read_array::<3>(), Some([1, 2, 3]));
+    /// ```
+    pub fn into_sink(self) -> ProducerSink<T, I> {
+        ProducerSink {
+            producer: self.into_async(),
+            pending: VecDeque::new(),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use core::ops::ControlFlow;
+use std::io::{self, IoSlice, IoSliceMut};
+
+use crate::{Consumer, Index, Producer};
+
+/// Writes bytes to the ring buffer without blocking.
+///
+/// A write copies as many bytes as fit into the free space. If the ring buffer
+/// is full, it fails with [`io::ErrorKind::WouldBlock`], or with
+/// [`io::ErrorKind::BrokenPipe`] once the consumer has been dropped, since the
+/// space will then never be freed. [`io::Write::flush`] does nothing, as the
+/// written bytes are readable right away.
+///
+/// These are the semantics of a nonblocking socket, so code driving one, which
+/// retries on `WouldBlock` once the peer makes progress, can drive the
+/// producer as is. Since [`io::Write::write_all`] gives up on `WouldBlock`, it
+/// only succeeds if the consumer keeps up. The inherent [`Producer::write`]
+/// method shadows [`io::Write::write`], which is thus called as
+/// `Write::write(&mut producer, buf)`.
+///
+/// This implementation is available only with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use std::io::{ErrorKind, Write};
+///
+/// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+/// producer.write_all(b"12-34").unwrap();
+/// assert_eq!(consumer.read_array::<5>(), Some(*b"12-34"));
+/// assert_eq!(Write::write(&mut producer, b"0123456789").unwrap(), 8);
+/// let error = Write::write(&mut producer, b"!").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::WouldBlock);
+/// ```
+impl<I: Index> io::Write for Producer<u8, I> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.write_from_slice(buf) {
+            0 if !self.is_consumer_alive() => Err(io::ErrorKind::BrokenPipe.into()),
+            0 => Err(io::ErrorKind::WouldBlock.into()),
+            written => Ok(written),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads bytes from the ring buffer without blocking.
+///
+/// A read copies as many of the readable bytes as fit into the buffer. If the
+/// ring buffer is empty, it fails with [`io::ErrorKind::WouldBlock`] while the
+/// producer is alive, and returns 0 bytes, the end of the stream, once the
+/// producer has been dropped. A reader thus never mistakes a momentarily empty
+/// ring buffer for the end of the data.
+///
+/// These are the semantics of a nonblocking socket, as for the [`io::Write`]
+/// implementation of [`Producer`]. Since [`io::Read::read_to_end`] and
+/// [`io::copy`] give up on `WouldBlock`, they only read everything if the
+/// producer has been dropped, or is dropped before the ring buffer runs empty.
+/// The inherent [`Consumer::read`] method shadows [`io::Read::read`], which is
+/// thus called as `Read::read(&mut consumer, buf)`.
+///
+/// This implementation is available only with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use std::io::{ErrorKind, Read};
+///
+/// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+/// let mut buf = [0; 4];
+/// let error = Read::read(&mut consumer, &mut buf).unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::WouldBlock);
+/// producer.write_exact(b"hello");
+/// drop(producer);
+/// let mut text = String::new();
+/// consumer.read_to_string(&mut text).unwrap();
+/// assert_eq!(text, "hello");
+/// ```
+impl<I: Index> io::Read for Consumer<u8, I> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // Checked first, so that the bytes written before the drop are seen.
+        let closed = !self.is_producer_alive();
+        match self.read_into_slice(buf) {
+            0 if closed => Ok(0),
+            0 => Err(io::ErrorKind::WouldBlock.into()),
+            read => Ok(read),
+        }
+    }
+}
+
+/// Exposes the readable bytes of the ring buffer without copying them.
+///
+/// [`io::BufRead::fill_buf`] returns the first contiguous readable slice of the
+/// storage, and [`io::BufRead::consume`] frees the bytes for the producer. A
+/// slice that ends at the wrap-around point is followed by the rest of the
+/// bytes once it has been consumed. Like [`io::Read::read`], `fill_buf` fails
+/// with [`io::ErrorKind::WouldBlock`] if the ring buffer is empty while the
+/// producer is alive, and returns an empty slice once it has been dropped.
+///
+/// Methods such as [`io::BufRead::read_until`] stop with `WouldBlock` when the
+/// ring buffer runs empty before the delimiter. The bytes read so far are then
+/// already in the output buffer, and the call can be repeated to complete it.
+///
+/// This implementation is available only with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::create_ring_buffer;
+/// use std::io::BufRead;
+///
+/// let (mut producer, consumer) = create_ring_buffer::<u8>(16);
+/// producer.write_exact(b"hello\nworld\n");
+/// drop(producer);
+/// let lines: Vec<String> = consumer.lines().map(Result::unwrap).collect();
+/// assert_eq!(lines, ["hello", "world"]);
+/// ```
+impl<I: Index> io::BufRead for Consumer<u8, I> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Checked first, so that the bytes written before the drop are seen.
+        let closed = !self.is_producer_alive();
+        let (first, _) = self.as_slices();
+        if first.is_empty() && !closed {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        Ok(first)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        debug_assert!(amt <= self.available(), "consumed more bytes than read");
+        self.advance(amt);
+    }
+}
+
+impl<I: Index> Consumer<u8, I> {
+    /// Returns the readable bytes of the ring buffer as `IoSlice`s.
+    ///
+    /// The slices are the ones of [`Consumer::as_slices`], so that a single
+    /// [`io::Write::write_vectored`] call, such as one `writev` system call,
+    /// writes the bytes across the wrap-around point without copying them
+    /// into a temporary buffer. The second slice is empty if the readable
+    /// bytes do not wrap around. The written bytes are freed only after calling
+    /// [`Consumer::advance`].
+    ///
+    /// This method is available only with the `std` feature.
+    ///
+    /// # Returns
+    ///
+    /// An array of two `IoSlice`s containing the readable bytes in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use std::io::Write;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(b"abcdef");
+    /// consumer.advance(4);
+    /// producer.write_exact(b"ghij");
+    /// let mut socket = Vec::new();
+    /// let written = socket.write_vectored(&consumer.io_slices()).unwrap();
+    /// assert_eq!(consumer.advance(written), 6);
+    /// assert_eq!(socket, b"efghij");
+    /// ```
+    pub fn io_slices(&self) -> [IoSlice<'_>; 2] {
+        let (first, second) = self.as_slices();
+        [IoSlice::new(first), IoSlice::new(second)]
+    }
+
+    /// Writes the readable bytes of the ring buffer into a writer.
+    ///
+    /// This method passes both readable slices of the storage to `writer`
+    /// directly, repeating the writes until all of them are written, and frees
+    /// the written bytes for the producer. Interrupted writes are retried. If
+    /// the writer fails after some bytes were written, the number of these
+    /// bytes is returned instead, and the writer is expected to report the
+    /// error again on the next call.
+    ///
+    /// This method is available only with the `std` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the bytes into.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the number of bytes written, which is 0 if the ring
+    /// buffer is empty, or `Err` containing the error of the writer if it
+    /// failed before writing any byte. A writer accepting no byte fails with
+    /// [`io::ErrorKind::WriteZero`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(b"abcdef");
+    /// consumer.advance(4);
+    /// producer.write_exact(b"ghij");
+    /// let mut file = Vec::new();
+    /// assert_eq!(consumer.write_to(&mut file).unwrap(), 6);
+    /// assert_eq!(file, b"efghij");
+    /// assert!(consumer.is_empty());
+    /// ```
+    pub fn write_to(&mut self, writer: &mut (impl io::Write + ?Sized)) -> io::Result<usize> {
+        let (written, error) = self.read_slices_until(
+            |data, _| {
+                let mut written = 0;
+                while written < data.len() {
+                    match writer.write(&data[written..]) {
+                        Ok(0) => {
+                            return (written, ControlFlow::Break(io::ErrorKind::WriteZero.into()))
+                        }
+                        // A misbehaving writer may report more than it was given.
+                        Ok(n) => written += n.min(data.len() - written),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(e) => return (written, ControlFlow::Break(e)),
+                    }
+                }
+                (written, ControlFlow::Continue(()))
+            },
+            None,
+        );
+        match error {
+            Some(e) if written == 0 => Err(e),
+            _ => Ok(written),
+        }
+    }
+}
+
+impl<I: Index> Producer<u8, I> {
+    /// Returns the free space of the ring buffer as `IoSliceMut`s.
+    ///
+    /// The slices are the ones of [`Producer::vacant_slices_mut`], so that a
+    /// single [`io::Read::read_vectored`] call, such as one `readv` system
+    /// call, fills the space across the wrap-around point without copying the
+    /// bytes from a temporary buffer. The second slice is empty if the free
+    /// space does not wrap around. The bytes read become readable only after
+    /// calling [`Producer::commit`].
+    ///
+    /// This method is available only with the `std` feature.
+    ///
+    /// # Returns
+    ///
+    /// An array of two `IoSliceMut`s covering the free space in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use std::io::Read;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(b"xxxxxx");
+    /// consumer.advance(6);
+    /// let mut socket: &[u8] = b"abcd";
+    /// let read = socket.read_vectored(&mut producer.io_slices_mut()).unwrap();
+    /// assert_eq!(producer.commit(read), 4);
+    /// assert_eq!(consumer.read_array::<4>(), Some(*b"abcd"));
+    /// ```
+    pub fn io_slices_mut(&mut self) -> [IoSliceMut<'_>; 2] {
+        let (first, second) = self.vacant_slices_mut();
+        [IoSliceMut::new(first), IoSliceMut::new(second)]
+    }
+
+    /// Reads bytes from a reader into the free space of the ring buffer.
+    ///
+    /// This method passes both writable slices of the storage to `reader`
+    /// directly, without an intermediate buffer, and makes the bytes read
+    /// readable for the consumer. The reads are repeated until the free space
+    /// is filled or the reader reaches its end. Interrupted reads are retried.
+    /// If the reader fails after some bytes were read, the number of these
+    /// bytes is returned instead, and the reader is expected to report the
+    /// error again on the next call.
+    ///
+    /// This method is available only with the `std` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to read the bytes from.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` containing the number of bytes read, which is 0 if the reader is
+    /// at its end or the ring buffer is full, or `Err` containing the error of
+    /// the reader if it failed before reading any byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// let mut file: &[u8] = b"0123456789";
+    /// assert_eq!(producer.read_from(&mut file).unwrap(), 8);
+    /// consumer.advance(6);
+    /// assert_eq!(producer.read_from(&mut file).unwrap(), 2);
+    /// assert_eq!(producer.read_from(&mut file).unwrap(), 0);
+    /// assert_eq!(consumer.read_array::<4>(), Some(*b"6789"));
+    /// ```
+    pub fn read_from(&mut self, reader: &mut (impl io::Read + ?Sized)) -> io::Result<usize> {
+        let (read, error) = self.write_slices_until(
+            |data, _| {
+                let mut read = 0;
+                while read < data.len() {
+                    match reader.read(&mut data[read..]) {
+                        Ok(0) => return (read, ControlFlow::Break(None)),
+                        // A misbehaving reader may report more than it was given.
+                        Ok(n) => read += n.min(data.len() - read),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(e) => return (read, ControlFlow::Break(Some(e))),
+                    }
+                }
+                (read, ControlFlow::Continue(()))
+            },
+            None,
+        );
+        match error {
+            Some(Some(e)) if read == 0 => Err(e),
+            _ => Ok(read),
+        }
+    }
+}
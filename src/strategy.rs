@@ -0,0 +1,149 @@
+use core::{fmt, hint};
+use std::thread;
+
+/// Policy deciding how a handle waits for the other one.
+///
+/// The blocking methods, such as
+/// [`Producer::write_blocking`](crate::Producer::write_blocking), call
+/// [`WaitStrategy::wait`] each time the condition they wait for does not hold
+/// yet, and check it again afterwards. The strategy can spin, yield or sleep
+/// itself, or ask for the thread to be parked until the other handle makes
+/// progress. It is set per ring buffer with
+/// [`RingBufferBuilder::wait_strategy`](crate::RingBufferBuilder::wait_strategy),
+/// and defaults to [`Park`].
+///
+/// This trait is available only with the `std-blocking` feature.
+///
+/// # Example
+///
+/// ```
+/// use direct_ring_buffer::{RingBufferBuilder, WaitStrategy};
+///
+/// // Sleeps instead of parking, for a thread that must not be unparked.
+/// #[derive(Debug)]
+/// struct Sleep;
+///
+/// impl WaitStrategy for Sleep {
+///     fn wait(&self, _attempt: u32) -> bool {
+///         std::thread::sleep(std::time::Duration::from_micros(100));
+///         false
+///     }
+/// }
+///
+/// let (mut producer, mut consumer) = RingBufferBuilder::<u8>::new()
+///     .capacity(4)
+///     .wait_strategy(Sleep)
+///     .build();
+/// let writer = std::thread::spawn(move || producer.write_blocking(&[1; 100]));
+/// let mut dst = [0; 100];
+/// assert_eq!(consumer.read_blocking(&mut dst), 100);
+/// assert_eq!(writer.join().unwrap(), 100);
+/// ```
+pub trait WaitStrategy: fmt::Debug + Send + Sync {
+    /// Waits before the condition is checked again.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The number of times this method has been called during
+    ///   the current wait, starting at zero.
+    ///
+    /// # Returns
+    ///
+    /// `true` to park the thread until the other handle makes progress, or
+    /// `false` to check the condition again right away.
+    fn wait(&self, attempt: u32) -> bool;
+}
+
+/// Parks the thread right away.
+///
+/// This is the default strategy. The thread uses no CPU time while it waits,
+/// but is woken up with the latency of the operating system scheduler.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Park;
+
+impl WaitStrategy for Park {
+    #[inline]
+    fn wait(&self, _attempt: u32) -> bool {
+        true
+    }
+}
+
+/// Spins on the condition without ever giving up the CPU.
+///
+/// This strategy has the lowest latency, at the cost of keeping a core busy
+/// for the whole wait. It suits threads pinned to dedicated cores.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BusySpin;
+
+impl WaitStrategy for BusySpin {
+    #[inline]
+    fn wait(&self, _attempt: u32) -> bool {
+        hint::spin_loop();
+        false
+    }
+}
+
+/// Spins for a number of attempts, then yields the CPU to other threads.
+#[derive(Debug, Clone, Copy)]
+pub struct SpinThenYield {
+    /// The number of attempts spent spinning before yielding.
+    pub spins: u32,
+}
+
+impl Default for SpinThenYield {
+    fn default() -> Self {
+        Self { spins: 100 }
+    }
+}
+
+impl WaitStrategy for SpinThenYield {
+    #[inline]
+    fn wait(&self, attempt: u32) -> bool {
+        if attempt < self.spins {
+            hint::spin_loop();
+        } else {
+            thread::yield_now();
+        }
+        false
+    }
+}
+
+/// Spins for exponentially longer periods, then yields, then parks.
+///
+/// Attempt `n` spins `2^n` times while `n` is below `spin_limit`, and yields
+/// the CPU while it is below `yield_limit`. Later attempts park the thread.
+/// Short waits are served with low latency, and long ones do not waste CPU
+/// time.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    /// The number of attempts spent spinning.
+    pub spin_limit: u32,
+    /// The number of attempts before parking, including the spinning ones.
+    pub yield_limit: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            spin_limit: 7,
+            yield_limit: 11,
+        }
+    }
+}
+
+impl WaitStrategy for Backoff {
+    #[inline]
+    fn wait(&self, attempt: u32) -> bool {
+        if attempt < self.spin_limit {
+            for _ in 0..1u32 << attempt.min(31) {
+                hint::spin_loop();
+            }
+            false
+        } else if attempt < self.yield_limit {
+            thread::yield_now();
+            false
+        } else {
+            true
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use alloc::vec::Vec;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::{FusedStream, Stream};
+
+use crate::{AsyncConsumer, Consumer, Index};
+
+/// Stream of the elements read from the ring buffer.
+///
+/// This struct is created by [`Consumer::into_stream`]. It yields the elements
+/// one by one as they are written, and ends once the ring buffer is empty and
+/// the producer has been dropped.
+///
+/// This struct is available only with the `futures` feature.
+#[derive(Debug)]
+pub struct ConsumerStream<T, I: Index = usize> {
+    consumer: AsyncConsumer<T, I>,
+}
+
+impl<T, I: Index> ConsumerStream<T, I> {
+    /// Unwraps the consumer.
+    pub fn into_inner(self) -> Consumer<T, I> {
+        self.consumer.into_inner()
+    }
+}
+
+impl<T, I: Index> Stream for ConsumerStream<T, I> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let consumer = &mut self.get_mut().consumer;
+        match consumer.poll_readable(cx) {
+            Poll::Ready(true) => Poll::Ready(consumer.get_mut().read_element()),
+            Poll::Ready(false) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.consumer.get_ref().available(), None)
+    }
+}
+
+impl<T, I: Index> FusedStream for ConsumerStream<T, I> {
+    fn is_terminated(&self) -> bool {
+        let buffer = &self.consumer.get_ref().buffer;
+        buffer.is_producer_dropped() && buffer.available_read() == 0
+    }
+}
+
+/// Stream of chunks of the elements read from the ring buffer.
+///
+/// This struct is created by [`Consumer::into_chunk_stream`]. Each time
+/// elements are available, it yields all of them, up to the maximum length of
+/// a chunk, as a `Vec`. It ends once the ring buffer is empty and the producer
+/// has been dropped.
+///
+/// This struct is available only with the `futures` feature.
+#[derive(Debug)]
+pub struct ChunkStream<T, I: Index = usize> {
+    consumer: AsyncConsumer<T, I>,
+    max_len: usize,
+}
+
+impl<T, I: Index> ChunkStream<T, I> {
+    /// Unwraps the consumer.
+    pub fn into_inner(self) -> Consumer<T, I> {
+        self.consumer.into_inner()
+    }
+}
+
+impl<T: Clone, I: Index> Stream for ChunkStream<T, I> {
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Vec<T>>> {
+        let this = self.get_mut();
+        match this.consumer.poll_readable(cx) {
+            Poll::Ready(true) => {
+                let consumer = this.consumer.get_mut();
+                let mut chunk = Vec::with_capacity(consumer.available().min(this.max_len));
+                consumer.read_slices(
+                    |data, _| {
+                        chunk.extend_from_slice(data);
+                        data.len()
+                    },
+                    Some(this.max_len),
+                );
+                Poll::Ready(Some(chunk))
+            }
+            Poll::Ready(false) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let available = self.consumer.get_ref().available();
+        (available.div_ceil(self.max_len), None)
+    }
+}
+
+impl<T: Clone, I: Index> FusedStream for ChunkStream<T, I> {
+    fn is_terminated(&self) -> bool {
+        let buffer = &self.consumer.get_ref().buffer;
+        buffer.is_producer_dropped() && buffer.available_read() == 0
+    }
+}
+
+/// Stream of fixed-size frames of the elements read from the ring buffer.
+///
+/// This struct is created by [`Consumer::frames`]. It yields arrays of `N`
+/// elements, waiting until a whole frame is available, and never yields a
+/// partial frame. It ends once fewer than `N` elements are left and the
+/// producer has been dropped. The elements of a partial frame are left in the
+/// ring buffer, where [`FrameStream::into_inner`] gives access to them.
+///
+/// This struct is available only with the `futures` feature.
+#[derive(Debug)]
+pub struct FrameStream<T, const N: usize, I: Index = usize> {
+    consumer: AsyncConsumer<T, I>,
+}
+
+impl<T, const N: usize, I: Index> FrameStream<T, N, I> {
+    /// Unwraps the consumer.
+    pub fn into_inner(self) -> Consumer<T, I> {
+        self.consumer.into_inner()
+    }
+}
+
+impl<T: Copy, const N: usize, I: Index> Stream for FrameStream<T, N, I> {
+    type Item = [T; N];
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<[T; N]>> {
+        let consumer = &mut self.get_mut().consumer;
+        match consumer.poll_available(cx, N) {
+            Poll::Ready(true) => Poll::Ready(consumer.get_mut().read_array()),
+            Poll::Ready(false) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.consumer.get_ref().available() / N, None)
+    }
+}
+
+impl<T: Copy, const N: usize, I: Index> FusedStream for FrameStream<T, N, I> {
+    fn is_terminated(&self) -> bool {
+        let buffer = &self.consumer.get_ref().buffer;
+        buffer.is_producer_dropped() && buffer.available_read() < N
+    }
+}
+
+impl<T, I: Index> Consumer<T, I> {
+    /// Converts the consumer into a stream of the elements.
+    ///
+    /// See [`ConsumerStream`] for details.
+    ///
+    /// This method is available only with the `futures` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::{executor::block_on, StreamExt};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// drop(producer);
+    /// let elements: Vec<u8> = block_on(consumer.into_stream().collect());
+    /// assert_eq!(elements, [1, 2, 3]);
+    /// ```
+    pub fn into_stream(self) -> ConsumerStream<T, I> {
+        ConsumerStream {
+            consumer: self.into_async(),
+        }
+    }
+
+    /// Converts the consumer into a stream of chunks of the elements.
+    ///
+    /// See [`ChunkStream`] for details.
+    ///
+    /// This method is available only with the `futures` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_len` - The maximum number of elements in a chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::{executor::block_on, StreamExt};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<u8>(4);
+    /// producer.write_exact(&[1, 2, 3]);
+    /// drop(producer);
+    /// let chunks: Vec<Vec<u8>> = block_on(consumer.into_chunk_stream(2).collect());
+    /// assert_eq!(chunks, [vec![1, 2], vec![3]]);
+    /// ```
+    pub fn into_chunk_stream(self, max_len: usize) -> ChunkStream<T, I> {
+        assert!(max_len > 0, "max_len must be greater than 0");
+        ChunkStream {
+            consumer: self.into_async(),
+            max_len,
+        }
+    }
+
+    /// Converts the consumer into a stream of frames of `N` elements.
+    ///
+    /// See [`FrameStream`] for details.
+    ///
+    /// This method is available only with the `futures` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0 or greater than the capacity of the ring buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    /// use futures::{executor::block_on, StreamExt};
+    ///
+    /// let (mut producer, consumer) = create_ring_buffer::<i16>(8);
+    /// producer.write_exact(&[1, 2, 3, 4, 5]);
+    /// drop(producer);
+    /// let frames: Vec<[i16; 2]> = block_on(consumer.frames::<2>().collect());
+    /// assert_eq!(frames, [[1, 2], [3, 4]]);
+    /// ```
+    pub fn frames<const N: usize>(self) -> FrameStream<T, N, I> {
+        assert!(N > 0, "N must be greater than 0");
+        assert!(
+            N <= self.capacity(),
+            "N must not be greater than the capacity"
+        );
+        FrameStream {
+            consumer: self.into_async(),
+        }
+    }
+}
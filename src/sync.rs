@@ -0,0 +1,135 @@
+//! Synchronization primitives shared by the handles.
+//!
+//! When the crate is built with `--cfg loom`, the primitives of
+//! [`loom`](https://docs.rs/loom) are used instead of the standard ones, so that
+//! the synchronization protocol can be model checked. With the
+//! `portable-atomic` feature, the primitives of
+//! [`portable-atomic`](https://docs.rs/portable-atomic) are used, so that the
+//! crate works on targets without native atomic read-modify-write operations.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{
+    atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicUsize, Ordering},
+    Arc,
+};
+
+#[cfg(all(not(loom), feature = "portable-atomic"))]
+pub(crate) use portable_atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicUsize, Ordering};
+#[cfg(all(not(loom), feature = "portable-atomic"))]
+pub(crate) use portable_atomic_util::Arc;
+
+#[cfg(all(not(loom), not(feature = "portable-atomic")))]
+pub(crate) use alloc::sync::Arc;
+#[cfg(all(not(loom), not(feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicUsize, Ordering};
+
+/// Ordering of the stores that may unblock a handle waiting for the other one.
+///
+/// When a waiting mechanism is built in, these stores are sequentially
+/// consistent, so that the handle making progress finds out whether anyone
+/// waits with a sequentially consistent load instead of a fence. Only the
+/// waiting handle places a fence, after announcing that it waits. The order of
+/// all sequentially consistent operations then guarantees that either the
+/// waiting handle sees the progress or the other handle sees it waiting.
+#[cfg(any(feature = "std-blocking", feature = "async", all(feature = "mio", unix)))]
+pub(crate) const PROGRESS: Ordering = Ordering::SeqCst;
+#[cfg(not(any(feature = "std-blocking", feature = "async", all(feature = "mio", unix))))]
+pub(crate) const PROGRESS: Ordering = Ordering::Release;
+
+/// Tracks the accesses to the slots of the storage.
+///
+/// With loom, every access is recorded on a cell per slot, so that the model
+/// checker reports accesses by the producer and the consumer to the same slot
+/// that are not ordered by the synchronization. Otherwise, this does nothing.
+#[cfg(loom)]
+pub(crate) struct Slots(alloc::boxed::Box<[loom::cell::UnsafeCell<()>]>);
+
+#[cfg(loom)]
+impl Slots {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self(
+            (0..capacity)
+                .map(|_| loom::cell::UnsafeCell::new(()))
+                .collect(),
+        )
+    }
+
+    /// Records an access to `len` slots starting at `start`.
+    pub(crate) fn access(&self, start: usize, len: usize) {
+        for slot in &self.0[start..start + len] {
+            slot.with_mut(|_| ());
+        }
+    }
+}
+
+#[cfg(not(loom))]
+pub(crate) struct Slots;
+
+#[cfg(not(loom))]
+impl Slots {
+    #[inline]
+    pub(crate) fn new(_capacity: usize) -> Self {
+        Self
+    }
+
+    /// Records an access to `len` slots starting at `start`.
+    #[inline]
+    pub(crate) fn access(&self, _start: usize, _len: usize) {}
+}
+
+/// Aligns a value to the size of a cache line, so that values modified by
+/// different threads do not share a line.
+///
+/// The alignment follows the line size of the target, including the adjacent
+/// line prefetched along with it on x86_64 and the large lines of the other
+/// 64-bit targets.
+#[cfg_attr(
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+    ),
+    repr(align(128))
+)]
+#[cfg_attr(
+    any(
+        target_arch = "arm",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+    ),
+    repr(align(32))
+)]
+#[cfg_attr(target_arch = "s390x", repr(align(256)))]
+#[cfg_attr(
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+        target_arch = "arm",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "s390x",
+    )),
+    repr(align(64))
+)]
+pub(crate) struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    #[inline]
+    pub(crate) const fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
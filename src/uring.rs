@@ -0,0 +1,178 @@
+use core::fmt;
+use std::io;
+
+use crate::{Index, Producer};
+
+/// Writable space of the ring buffer as `iovec`s for io_uring.
+///
+/// This struct is created by [`Producer::uring_iovecs`]. It holds one `iovec`
+/// per contiguous writable region, which is two if the space wraps around the
+/// end of the storage. The `iovec`s can be submitted with `IORING_OP_READV`, or
+/// the first one with `IORING_OP_READ_FIXED` if the storage has been registered
+/// with [`Producer::uring_buffer`]. Either way, the kernel fills the regions in
+/// order, so the result of the completion is passed as is to
+/// [`Producer::uring_complete`].
+///
+/// This struct is available only with the `io-uring` feature, on Linux.
+#[derive(Clone, Copy)]
+pub struct IoVecs {
+    iovecs: [libc::iovec; 2],
+    count: usize,
+}
+
+impl IoVecs {
+    /// Returns a pointer to the `iovec`s, for the `iovec` field of the
+    /// submission.
+    pub fn as_ptr(&self) -> *const libc::iovec {
+        self.iovecs.as_ptr()
+    }
+
+    /// Returns the number of `iovec`s, which is 0 if the ring buffer is full.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if there is no writable space.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the `iovec`s as a slice.
+    pub fn as_slice(&self) -> &[libc::iovec] {
+        &self.iovecs[..self.count]
+    }
+
+    /// Returns the total number of bytes described by the `iovec`s.
+    pub fn total_len(&self) -> usize {
+        self.as_slice().iter().map(|iovec| iovec.iov_len).sum()
+    }
+}
+
+impl fmt::Debug for IoVecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.as_slice()
+                    .iter()
+                    .map(|iovec| (iovec.iov_base, iovec.iov_len)),
+            )
+            .finish()
+    }
+}
+
+// The iovecs only describe the storage, which the producer owns.
+unsafe impl Send for IoVecs {}
+unsafe impl Sync for IoVecs {}
+
+impl<I: Index> Producer<u8, I> {
+    /// Returns the whole storage as an `iovec`, for registering it as a fixed
+    /// buffer of io_uring.
+    ///
+    /// Registering the storage once with `IORING_REGISTER_BUFFERS` lets the
+    /// kernel write into it with `IORING_OP_READ_FIXED` without mapping the
+    /// pages on every operation. The storage never moves while the ring buffer
+    /// exists, so the registration stays valid until the ring buffer is
+    /// dropped, and must be removed before that.
+    ///
+    /// This method is available only with the `io-uring` feature, on Linux.
+    pub fn uring_buffer(&self) -> libc::iovec {
+        libc::iovec {
+            iov_base: self.as_mut_ptr().cast(),
+            iov_len: self.capacity(),
+        }
+    }
+
+    /// Returns the writable space as `iovec`s for an io_uring read.
+    ///
+    /// The kernel writes directly into the storage, without an intermediate
+    /// copy. See [`IoVecs`] for how to submit them. Until the completion has
+    /// been passed to [`Producer::uring_complete`], no other write must be made
+    /// to the ring buffer, since it would target the same space.
+    ///
+    /// This method is available only with the `io-uring` feature, on Linux.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of bytes to describe.
+    ///
+    /// # Returns
+    ///
+    /// The `iovec`s describing up to `max` writable bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use direct_ring_buffer::create_ring_buffer;
+    ///
+    /// let (mut producer, mut consumer) = create_ring_buffer::<u8>(8);
+    /// producer.write_exact(&[0; 6]);
+    /// consumer.advance(6);
+    /// let iovecs = producer.uring_iovecs(usize::MAX);
+    /// assert_eq!(iovecs.len(), 2);
+    /// assert_eq!(iovecs.total_len(), 8);
+    ///
+    /// // Stands in for an `IORING_OP_READV` submission and its completion.
+    /// let mut fds = [0; 2];
+    /// unsafe {
+    ///     assert_eq!(libc::pipe(fds.as_mut_ptr()), 0);
+    ///     libc::write(fds[1], b"0123456789".as_ptr().cast(), 10);
+    ///     let result = libc::readv(fds[0], iovecs.as_ptr(), iovecs.len() as i32);
+    ///     assert_eq!(producer.uring_complete(result as i32).unwrap(), 8);
+    ///     libc::close(fds[0]);
+    ///     libc::close(fds[1]);
+    /// }
+    /// assert_eq!(consumer.read_array::<8>(), Some(*b"01234567"));
+    /// ```
+    pub fn uring_iovecs(&mut self, max: usize) -> IoVecs {
+        let base = self.as_mut_ptr();
+        let (first, second) = self.writable_regions();
+        let mut iovecs = IoVecs {
+            iovecs: [libc::iovec {
+                iov_base: core::ptr::null_mut(),
+                iov_len: 0,
+            }; 2],
+            count: 0,
+        };
+        let mut remaining = max;
+        for region in [first, second] {
+            let len = region.len.min(remaining);
+            if len == 0 {
+                break;
+            }
+            iovecs.iovecs[iovecs.count] = libc::iovec {
+                iov_base: unsafe { base.add(region.offset) }.cast(),
+                iov_len: len,
+            };
+            iovecs.count += 1;
+            remaining -= len;
+        }
+        iovecs
+    }
+
+    /// Makes the bytes written by an io_uring read available for reading.
+    ///
+    /// This method is available only with the `io-uring` feature, on Linux.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The `res` field of the completion of a read submitted with
+    ///   the `iovec`s returned by [`Producer::uring_iovecs`].
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes made available, or the error reported by the
+    /// completion if `result` is negative.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `result` is the result of a read into the
+    /// last `iovec`s returned by [`Producer::uring_iovecs`], made while no
+    /// other write was made to the ring buffer, so that the kernel has written
+    /// the first `result` bytes of the writable space.
+    pub unsafe fn uring_complete(&mut self, result: i32) -> io::Result<usize> {
+        if result < 0 {
+            return Err(io::Error::from_raw_os_error(-result));
+        }
+        Ok(self.advance(result as usize))
+    }
+}
@@ -0,0 +1,107 @@
+use core::{cell::UnsafeCell, fmt, sync::atomic::fence, task::Waker};
+
+use crate::sync::{AtomicBool, AtomicUsize, Ordering};
+
+/// No thread is accessing the waker.
+const IDLE: usize = 0;
+/// A task is storing its waker.
+const REGISTERING: usize = 0b01;
+/// The other handle is taking the waker to wake it.
+const WAKING: usize = 0b10;
+
+/// Slot in which a task registers its waker until the other handle makes
+/// progress.
+///
+/// The waker is stored and taken under a small state machine, so that a
+/// wakeup racing with a registration is never lost: if the other handle finds
+/// the slot being registered, it leaves `WAKING` set, and the registering task
+/// wakes itself once it is done.
+///
+/// Like [`WaitSlot`](crate::blocking::WaitSlot), the task sets `waiting` and
+/// checks its condition again after registering, with a sequentially
+/// consistent fence in between, and the other handle checks `waiting` with a
+/// sequentially consistent load after storing a position with
+/// [`PROGRESS`](crate::sync::PROGRESS) ordering. The handle that makes progress
+/// thus only pays for the load while no task waits.
+#[derive(Default)]
+pub(crate) struct WakerSlot {
+    state: AtomicUsize,
+    waiting: AtomicBool,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+impl WakerSlot {
+    /// Registers the waker of the current task to be woken by the next wakeup.
+    ///
+    /// The caller checks its condition again afterwards.
+    pub(crate) fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(IDLE, REGISTERING, Ordering::Acquire, Ordering::Acquire)
+        {
+            Ok(_) => {
+                // Only this task accesses the waker until the state is reset.
+                let slot = unsafe { &mut *self.waker.get() };
+                if !slot.as_ref().is_some_and(|old| old.will_wake(waker)) {
+                    *slot = Some(waker.clone());
+                }
+                if self
+                    .state
+                    .compare_exchange(REGISTERING, IDLE, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    // A wakeup came in while registering, and left the waker
+                    // to this task.
+                    let waker = slot.take();
+                    self.state.swap(IDLE, Ordering::AcqRel);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                    return;
+                }
+            }
+            Err(_) => {
+                // A wakeup is in progress, or another task is registering
+                // through a shared handle. The task is woken right away to
+                // check its condition again.
+                waker.wake_by_ref();
+                return;
+            }
+        }
+        self.waiting.store(true, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+    }
+
+    /// Wakes the task registered in the slot, if any.
+    #[inline]
+    pub(crate) fn wake(&self) {
+        if self.waiting.load(Ordering::SeqCst) && self.waiting.swap(false, Ordering::Relaxed) {
+            self.take_and_wake();
+        }
+    }
+
+    fn take_and_wake(&self) {
+        // If the slot is being registered, the task sees `WAKING` and wakes
+        // itself.
+        if self.state.fetch_or(WAKING, Ordering::AcqRel) == IDLE {
+            // Only this thread accesses the waker until the state is reset.
+            let waker = unsafe { (*self.waker.get()).take() };
+            self.state.fetch_and(!WAKING, Ordering::Release);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for WakerSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WakerSlot")
+            .field("waiting", &self.waiting)
+            .finish_non_exhaustive()
+    }
+}
+
+// The waker is only accessed by the thread owning the state.
+unsafe impl Send for WakerSlot {}
+unsafe impl Sync for WakerSlot {}
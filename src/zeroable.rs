@@ -0,0 +1,46 @@
+use core::{marker::PhantomData, mem::MaybeUninit, num::Wrapping, ptr::NonNull};
+
+/// Types for which a value with all bytes set to zero is valid.
+///
+/// This trait is used by
+/// [`create_ring_buffer_zeroed`](crate::create_ring_buffer_zeroed) to treat the
+/// zero-filled storage as initialized elements.
+///
+/// # Safety
+///
+/// An all-zero bit pattern must be a valid value of the implementing type.
+pub unsafe trait Zeroable: Sized {}
+
+macro_rules! impl_zeroable {
+    ($($t:ty),* $(,)?) => {
+        $(unsafe impl Zeroable for $t {})*
+    };
+}
+
+impl_zeroable!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+unsafe impl<T: Zeroable> Zeroable for Wrapping<T> {}
+unsafe impl<T> Zeroable for MaybeUninit<T> {}
+unsafe impl<T: ?Sized> Zeroable for PhantomData<T> {}
+unsafe impl<T> Zeroable for *const T {}
+unsafe impl<T> Zeroable for *mut T {}
+unsafe impl<T> Zeroable for Option<NonNull<T>> {}
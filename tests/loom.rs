@@ -0,0 +1,82 @@
+//! Model checks of the synchronization between the producer and the consumer.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+
+use direct_ring_buffer::create_ring_buffer;
+use loom::thread;
+
+#[test]
+fn loom_element_read_write() {
+    loom::model(|| {
+        let (mut p, mut c) = create_ring_buffer::<usize>(2);
+        let producer = thread::spawn(move || {
+            for value in 0..3 {
+                while !p.write_element(value) {
+                    thread::yield_now();
+                }
+            }
+        });
+        for value in 0..3 {
+            let read = loop {
+                if let Some(read) = c.read_element() {
+                    break read;
+                }
+                thread::yield_now();
+            };
+            assert_eq!(read, value);
+        }
+        producer.join().unwrap();
+    });
+}
+
+#[test]
+fn loom_slices_read_write() {
+    loom::model(|| {
+        let (mut p, mut c) = create_ring_buffer::<usize>(3);
+        let producer = thread::spawn(move || {
+            let mut next = 0;
+            while next < 4 {
+                next += p.write_slices(
+                    |data, offset| {
+                        for (i, value) in data.iter_mut().enumerate() {
+                            *value = next + offset + i;
+                        }
+                        data.len()
+                    },
+                    Some(2.min(4 - next)),
+                );
+                thread::yield_now();
+            }
+        });
+        let mut next = 0;
+        while next < 4 {
+            c.read_slices(
+                |data, _| {
+                    for value in data {
+                        assert_eq!(*value, next);
+                        next += 1;
+                    }
+                    data.len()
+                },
+                None,
+            );
+            thread::yield_now();
+        }
+        producer.join().unwrap();
+    });
+}
+
+#[test]
+fn loom_drop_unread() {
+    loom::model(|| {
+        let (mut p, mut c) = create_ring_buffer::<String>(2);
+        assert!(p.write_element("a".to_string()));
+        let consumer = thread::spawn(move || {
+            drop(c.read_element());
+        });
+        assert!(p.write_element("b".to_string()));
+        drop(p);
+        consumer.join().unwrap();
+    });
+}
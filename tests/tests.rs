@@ -1,8 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use direct_ring_buffer::{create_ring_buffer, Consumer, Producer};
+    use direct_ring_buffer::{
+        create_ring_buffer, create_ring_buffer_default, create_ring_buffer_from,
+        create_ring_buffer_from_iter, create_ring_buffer_from_with_capacity,
+        create_ring_buffer_with_index, create_ring_buffer_zeroed, try_create_ring_buffer, Consumer,
+        CreateError, Producer, Region, ReuniteError, RingBufferBuilder, TryReadError,
+    };
     use rand::Rng;
-    use std::thread;
+    use std::{
+        mem::MaybeUninit,
+        ops::ControlFlow,
+        panic::{self, AssertUnwindSafe},
+        thread,
+    };
 
     #[test]
     fn test_empty() {
@@ -12,6 +22,730 @@ mod tests {
         assert_eq!(c.read_slices(|data, _offset| data.len(), None), 0);
     }
 
+    #[test]
+    fn test_try_create() {
+        let (p, c) = try_create_ring_buffer::<u16>(3).unwrap();
+        assert_eq!((p.capacity(), c.capacity(), p.available()), (3, 3, 3));
+        assert_eq!(
+            try_create_ring_buffer::<u16>(0).unwrap_err(),
+            CreateError::ZeroCapacity
+        );
+        assert_eq!(
+            try_create_ring_buffer::<u16>(usize::MAX / 2).unwrap_err(),
+            CreateError::CapacityOverflow
+        );
+        // Miri does not support reporting allocation failures.
+        #[cfg(not(miri))]
+        assert_eq!(
+            try_create_ring_buffer::<u8>(isize::MAX as usize).unwrap_err(),
+            CreateError::AllocationFailed
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let (mut p, mut c) = RingBufferBuilder::<u32>::new().capacity(4).zeroed().build();
+        assert_eq!((p.capacity(), p.available(), c.available()), (4, 4, 0));
+        assert_eq!(p.vacant_slices_mut().0, &[0, 0, 0, 0]);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+
+        let builder = RingBufferBuilder::<u32>::default().zeroed();
+        let (p, _c) = builder.clone().capacity(2).try_build().unwrap();
+        assert_eq!(p.capacity(), 2);
+        assert_eq!(builder.try_build().unwrap_err(), CreateError::ZeroCapacity);
+    }
+
+    #[test]
+    fn test_create_from_vec() {
+        let (mut p, mut c) = create_ring_buffer_from(vec![1u8, 2, 3]);
+        assert_eq!(p.capacity(), 3);
+        assert!(p.is_full());
+        assert_eq!(p.total_written(), 3);
+        assert_eq!(c.read_element(), Some(1));
+        assert!(p.write_element(4));
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let (mut p, mut c) = create_ring_buffer_from_with_capacity(vec![1u8, 2], 4);
+        assert_eq!((p.capacity(), p.available(), c.available()), (4, 2, 2));
+        assert!(p.write_exact(&[3, 4]));
+        assert_eq!(c.read_array::<4>(), Some([1, 2, 3, 4]));
+
+        let (p, c) = create_ring_buffer_from(Vec::<u8>::new());
+        assert_eq!((p.capacity(), c.available()), (0, 0));
+    }
+
+    #[test]
+    fn test_create_from_iter() {
+        let (mut p, mut c, taken) = create_ring_buffer_from_iter(4, [1u8, 2]);
+        assert_eq!(taken, 2);
+        assert_eq!((p.capacity(), p.available(), c.available()), (4, 2, 2));
+        assert!(p.write_exact(&[3, 4]));
+        assert_eq!(c.read_array::<4>(), Some([1, 2, 3, 4]));
+
+        let mut iter = 1u8..=6;
+        let (_p, mut c, taken) = create_ring_buffer_from_iter(4, &mut iter);
+        assert_eq!(taken, 4);
+        assert_eq!(iter.next(), Some(5));
+        assert_eq!(c.read_array::<4>(), Some([1, 2, 3, 4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_from_vec_over_capacity() {
+        create_ring_buffer_from_with_capacity(vec![1u8, 2, 3], 2);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(10);
+        assert_eq!(p.capacity(), 10);
+        assert_eq!(c.capacity(), 10);
+        assert_eq!(p.write_slices(|data, _offset| data.len(), Some(4)), 4);
+        assert_eq!(p.capacity(), 10);
+        assert_eq!(c.capacity(), 10);
+        assert_eq!(c.read_slices(|data, _offset| data.len(), None), 4);
+        assert_eq!(p.capacity(), 10);
+        assert_eq!(c.capacity(), 10);
+    }
+
+    #[test]
+    fn test_is_empty_is_full() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        assert!(p.is_empty() && c.is_empty());
+        assert!(!p.is_full() && !c.is_full());
+        assert!(p.write_element(1));
+        assert!(!p.is_empty() && !c.is_empty());
+        assert!(!p.is_full() && !c.is_full());
+        assert_eq!(p.write_slices(|data, _offset| data.len(), None), 2);
+        assert!(!p.is_empty() && !c.is_empty());
+        assert!(p.is_full() && c.is_full());
+        assert_eq!(c.read_slices(|data, _offset| data.len(), None), 3);
+        assert!(p.is_empty() && c.is_empty());
+        assert!(!p.is_full() && !c.is_full());
+    }
+
+    #[test]
+    fn test_is_peer_alive() {
+        let (mut p, c) = create_ring_buffer::<u8>(3);
+        assert!(p.is_consumer_alive() && c.is_producer_alive());
+        drop(c);
+        assert!(!p.is_consumer_alive());
+        // Writes still succeed, but nothing reads them.
+        assert!(p.write_element(1));
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        assert!(p.write_exact(&[1, 2]));
+        drop(p);
+        assert!(!c.is_producer_alive());
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(c.read_element(), Some(2));
+        assert_eq!(c.read_element(), None);
+    }
+
+    #[test]
+    fn test_try_read_empty_closed() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(c.try_read_element(), Err(TryReadError::Empty));
+        assert_eq!(
+            c.try_read_slices(|data, _| data.len(), None),
+            Err(TryReadError::Empty)
+        );
+        assert!(p.write_exact(&[1, 2, 3]));
+        drop(p);
+        // The elements written before the drop are read first.
+        assert_eq!(c.try_read_element(), Ok(1));
+        assert_eq!(c.try_read_slices(|data, _| data.len(), Some(1)), Ok(1));
+        assert_eq!(c.try_read_slices(|_, _| 0, None), Ok(0));
+        assert_eq!(c.try_read_slices(|data, _| data.len(), None), Ok(1));
+        assert_eq!(c.try_read_element(), Err(TryReadError::Closed));
+        assert_eq!(
+            c.try_read_slices(|data, _| data.len(), None),
+            Err(TryReadError::Closed)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_write() {
+        use std::io::{ErrorKind, Write};
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert_eq!(Write::write(&mut p, &[]).unwrap(), 0);
+        assert_eq!(Write::write(&mut p, b"abcdef").unwrap(), 6);
+        assert_eq!(c.advance(4), 4);
+        // Wraps around the end of the buffer.
+        assert_eq!(Write::write(&mut p, b"0123456789").unwrap(), 6);
+        assert_eq!(
+            Write::write(&mut p, b"x").unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+        assert_eq!(p.write_all(b"x").unwrap_err().kind(), ErrorKind::WouldBlock);
+        p.flush().unwrap();
+        let mut received = [0; 8];
+        assert!(c.read_exact_into(&mut received));
+        assert_eq!(&received, b"ef012345");
+
+        std::io::copy(&mut &b"hello"[..], &mut p).unwrap();
+        assert_eq!(c.read_array::<5>(), Some(*b"hello"));
+        drop(c);
+        assert_eq!(Write::write(&mut p, b"abcdefgh").unwrap(), 8);
+        assert_eq!(
+            Write::write(&mut p, b"x").unwrap_err().kind(),
+            ErrorKind::BrokenPipe
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_read() {
+        use std::io::{ErrorKind, Read};
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        let mut buf = [0; 6];
+        assert_eq!(Read::read(&mut c, &mut []).unwrap(), 0);
+        assert_eq!(
+            Read::read(&mut c, &mut buf).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+        assert!(p.write_exact(b"abcdef"));
+        assert_eq!(Read::read(&mut c, &mut buf[..4]).unwrap(), 4);
+        assert_eq!(&buf[..4], b"abcd");
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(b"012345"));
+        assert_eq!(Read::read(&mut c, &mut buf).unwrap(), 6);
+        assert_eq!(&buf, b"ef0123");
+        assert_eq!(
+            c.read_exact(&mut buf).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(b"hello"));
+        drop(p);
+        let mut received = Vec::new();
+        std::io::copy(&mut c, &mut received).unwrap();
+        assert_eq!(received, b"hello");
+        assert_eq!(Read::read(&mut c, &mut buf).unwrap(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_buf_read() {
+        use std::io::{BufRead, ErrorKind};
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert_eq!(c.fill_buf().unwrap_err().kind(), ErrorKind::WouldBlock);
+        assert!(p.write_exact(b"abcdef"));
+        c.consume(4);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(b"gh\nhi"));
+        assert_eq!(c.fill_buf().unwrap(), b"efgh");
+        let mut line = Vec::new();
+        assert_eq!(c.read_until(b'\n', &mut line).unwrap(), 5);
+        assert_eq!(line, b"efgh\n");
+        line.clear();
+        // Stops when the ring buffer runs empty, keeping the bytes read so far.
+        assert_eq!(
+            c.read_until(b'\n', &mut line).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+        assert_eq!(line, b"hi");
+        assert!(p.write_exact(b"!\nend"));
+        assert_eq!(c.read_until(b'\n', &mut line).unwrap(), 2);
+        assert_eq!(line, b"hi!\n");
+        drop(p);
+        let lines: Vec<String> = c.lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["end"]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to() {
+        use std::io::{self, ErrorKind, Write};
+
+        /// Accepts up to `limit` bytes, at most 3 per write, interrupting
+        /// every other write.
+        struct Limited {
+            data: Vec<u8>,
+            limit: usize,
+            interrupt: bool,
+        }
+
+        impl Write for Limited {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.interrupt = !self.interrupt;
+                if self.interrupt {
+                    return Err(ErrorKind::Interrupted.into());
+                }
+                let len = buf.len().min(3).min(self.limit - self.data.len());
+                if len == 0 {
+                    return Err(ErrorKind::WouldBlock.into());
+                }
+                self.data.extend_from_slice(&buf[..len]);
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        let mut writer = Limited {
+            data: Vec::new(),
+            limit: 7,
+            interrupt: false,
+        };
+        assert_eq!(c.write_to(&mut writer).unwrap(), 0);
+        assert!(p.write_exact(b"abcdef"));
+        assert_eq!(c.advance(4), 4);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(b"ghijkl"));
+        assert_eq!(c.write_to(&mut writer).unwrap(), 7);
+        assert_eq!(writer.data, b"efghijk");
+        assert_eq!(
+            c.write_to(&mut writer).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+        assert_eq!(c.available(), 1);
+        assert_eq!(
+            c.write_to(&mut &mut [][..]).unwrap_err().kind(),
+            ErrorKind::WriteZero
+        );
+        let mut rest = Vec::new();
+        assert_eq!(c.write_to(&mut rest as &mut dyn Write).unwrap(), 1);
+        assert_eq!(rest, b"l");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_from() {
+        use std::io::{self, ErrorKind, Read};
+
+        /// Yields its data at most 3 bytes per read, interrupting every other
+        /// read, and would block once the data has been read.
+        struct Limited {
+            data: Vec<u8>,
+            interrupt: bool,
+        }
+
+        impl Read for Limited {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.interrupt = !self.interrupt;
+                if self.interrupt {
+                    return Err(ErrorKind::Interrupted.into());
+                }
+                if self.data.is_empty() {
+                    return Err(ErrorKind::WouldBlock.into());
+                }
+                let len = buf.len().min(3).min(self.data.len());
+                buf[..len].copy_from_slice(&self.data[..len]);
+                self.data.drain(..len);
+                Ok(len)
+            }
+        }
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        let mut reader = Limited {
+            data: b"abcdefghijk".to_vec(),
+            interrupt: false,
+        };
+        assert!(p.write_exact(b"xxxx"));
+        assert_eq!(c.advance(4), 4);
+        // Wraps around the end of the buffer.
+        assert_eq!(p.read_from(&mut reader).unwrap(), 8);
+        assert_eq!(p.read_from(&mut reader).unwrap(), 0);
+        assert_eq!(c.read_array::<8>(), Some(*b"abcdefgh"));
+        assert_eq!(p.read_from(&mut reader).unwrap(), 3);
+        assert_eq!(
+            p.read_from(&mut reader).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+        assert_eq!(p.read_from(&mut io::empty() as &mut dyn Read).unwrap(), 0);
+        assert_eq!(c.read_array::<3>(), Some(*b"ijk"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to_over_reporting() {
+        use std::io::{self, Write};
+
+        /// Claims to have written more bytes than it was given.
+        struct Liar;
+
+        impl Write for Liar {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                Ok(buf.len() + 1000)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(b"abcd"));
+        assert_eq!(c.write_to(&mut Liar).unwrap(), 4);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.available(), 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_from_over_reporting() {
+        use std::io::{self, Read};
+
+        /// Claims to have read more bytes than it was given.
+        struct Liar;
+
+        impl Read for Liar {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                buf.fill(b'x');
+                Ok(buf.len() + 1000)
+            }
+        }
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(p.read_from(&mut Liar).unwrap(), 4);
+        assert_eq!(p.available(), 0);
+        assert_eq!(c.advance(4), 4);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.available(), 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_slices() {
+        use std::io::{Read, Write};
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        let [first, second] = p.io_slices_mut();
+        assert_eq!((first.len(), second.len()), (8, 0));
+        assert!(p.write_exact(b"xxxxx"));
+        assert_eq!(c.advance(5), 5);
+        // Wraps around the end of the buffer.
+        let mut reader: &[u8] = b"abcdefghij";
+        let read = reader.read_vectored(&mut p.io_slices_mut()).unwrap();
+        assert_eq!(read, 8);
+        assert_eq!(p.commit(read), 8);
+        assert!(p.io_slices_mut().iter().all(|slice| slice.is_empty()));
+
+        let [first, second] = c.io_slices();
+        assert_eq!((&*first, &*second), (&b"abc"[..], &b"defgh"[..]));
+        let mut writer = Vec::new();
+        let written = writer.write_vectored(&c.io_slices()).unwrap();
+        assert_eq!(c.advance(written), 8);
+        assert_eq!(writer, b"abcdefgh");
+        assert!(c.io_slices().iter().all(|slice| slice.is_empty()));
+    }
+
+    #[test]
+    fn test_read_until_delimiter() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(6);
+        assert_eq!(c.read_until_delimiter(b'\n'), None);
+        assert!(p.write_exact(b"ab\ncd"));
+        assert_eq!(c.read_until_delimiter(b'\n'), Some(b"ab\n".to_vec()));
+        assert_eq!(c.read_until_delimiter(b'\n'), None);
+        assert_eq!(c.available(), 2);
+        // The delimiter wraps around the end of the buffer.
+        assert!(p.write_exact(b"ef\n"));
+        assert_eq!(c.as_slices(), (&b"cde"[..], &b"f\n"[..]));
+        assert_eq!(c.read_until_delimiter(b'\n'), Some(b"cdef\n".to_vec()));
+        // The delimiter ends the first slice.
+        assert!(p.write_exact(b"ghi\n"));
+        assert_eq!(c.as_slices(), (&b"ghi\n"[..], &[][..]));
+        assert_eq!(c.read_until_delimiter(b'\n'), Some(b"ghi\n".to_vec()));
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn test_fmt_write() {
+        use std::fmt::Write;
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(b"xxxxx"));
+        assert_eq!(c.advance(5), 5);
+        // Wraps around the end of the buffer.
+        write!(p, "{}-{}", 12, 345).unwrap();
+        assert_eq!(c.read_array::<6>(), Some(*b"12-345"));
+        // The whole output is written, or nothing at all.
+        assert!(write!(p, "{}-{}", 1234, 5678).is_err());
+        assert!(c.is_empty());
+        let word = "static";
+        writeln!(p, "{word}").unwrap();
+        assert!(writeln!(p, "!").is_err());
+        assert_eq!(c.read_array::<7>(), Some(*b"static\n"));
+        // Through the trait, each string is written whole or not at all.
+        let (head, tail) = ("abcde", "fgh!");
+        assert!(Write::write_fmt(&mut p, format_args!("{head}{tail}")).is_err());
+        assert_eq!(c.read_array::<5>(), Some(*b"abcde"));
+        p.write_char('é').unwrap();
+        assert_eq!(c.read_array::<2>(), Some([0xc3, 0xa9]));
+    }
+
+    #[test]
+    fn test_endian_accessors() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(16);
+        assert!(p.write_exact(&[0; 14]));
+        assert_eq!(c.advance(14), 14);
+        // Each value wraps around the end of the buffer.
+        assert!(p.write_u32_le(0x0102_0304));
+        assert_eq!(c.as_slices(), (&[4, 3][..], &[2, 1][..]));
+        assert_eq!(c.read_u32_le(), Some(0x0102_0304));
+        assert!(p.write_u64_be(0x0102_0304_0506_0708));
+        assert_eq!(c.read_array::<8>(), Some([1, 2, 3, 4, 5, 6, 7, 8]));
+        assert!(p.write_i16_be(-2));
+        assert_eq!(
+            c.read_i16_le(),
+            Some(i16::from_le_bytes((-2i16).to_be_bytes()))
+        );
+        assert!(p.write_f64_le(1.5));
+        assert_eq!(c.read_f64_le(), Some(1.5));
+        assert!(p.write_u128_be(u128::MAX - 1));
+        assert!(!p.write_u16_le(1));
+        assert_eq!(c.read_u128_be(), Some(u128::MAX - 1));
+        assert!(p.write_u16_le(0xabcd));
+        assert_eq!(c.read_u32_le(), None);
+        assert_eq!(c.available(), 2);
+        assert_eq!(c.read_u16_le(), Some(0xabcd));
+    }
+
+    #[test]
+    fn test_make_room() {
+        let (mut p, mut c) = create_ring_buffer::<String>(3);
+        assert_eq!(p.make_room(&mut c, 2), 0);
+        for value in ["a", "b", "c"] {
+            assert!(p.write_element(value.to_string()));
+        }
+        assert_eq!(p.make_room(&mut c, 2), 2);
+        assert_eq!(c.total_read(), 2);
+        assert!(p.write_element("d".to_string()));
+        assert!(p.write_element("e".to_string()));
+        assert_eq!(p.make_room(&mut c, 10), 3);
+        assert_eq!((p.available(), c.available()), (3, 0));
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        let mut observer = p.observer().unwrap();
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        // Nothing is evicted while the observer still holds the space.
+        assert_eq!(p.make_room(&mut c, 1), 0);
+        assert_eq!(c.available(), 4);
+        assert_eq!(observer.advance(1), 1);
+        // Only the space released by the observer is made.
+        assert_eq!(p.make_room(&mut c, 3), 1);
+        assert_eq!(p.available(), 1);
+        assert!(p.write_element(5));
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), [2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "consumer belongs to a different ring buffer")]
+    fn test_make_room_different_buffer() {
+        let (mut p, _c) = create_ring_buffer::<u8>(3);
+        let (_p, mut other) = create_ring_buffer::<u8>(3);
+        p.make_room(&mut other, 1);
+    }
+
+    #[test]
+    fn test_overwrite_writes() {
+        let (mut p, mut c) = create_ring_buffer::<String>(2);
+        assert_eq!(p.force_write_element(&mut c, "a".to_string()), Ok(None));
+        assert_eq!(p.force_write_element(&mut c, "b".to_string()), Ok(None));
+        assert_eq!(
+            p.force_write_element(&mut c, "c".to_string()),
+            Ok(Some("a".to_string()))
+        );
+        assert_eq!(c.read_element().as_deref(), Some("b"));
+        assert_eq!(c.read_element().as_deref(), Some("c"));
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        // Wraps around the end of the buffer.
+        assert_eq!(p.write_from_slice_overwrite(&mut c, &[4, 5, 6, 7, 8]), 1);
+        assert_eq!(c.total_read(), 3);
+        assert_eq!(c.as_slices(), (&[4, 5][..], &[6, 7, 8][..]));
+        assert_eq!(p.write_from_slice_overwrite(&mut c, &[]), 0);
+        assert_eq!(p.write_from_slice_overwrite(&mut c, &[0; 12]), 12);
+        assert_eq!(c.total_read(), 8);
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), [0; 5]);
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        let observer = p.observer().unwrap();
+        assert!(p.write_exact(&[1, 2]));
+        // The observer still holds the space, so only the last element fits.
+        assert_eq!(p.write_from_slice_overwrite(&mut c, &[3, 4, 5]), 2);
+        assert_eq!(c.total_read(), 0);
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), [1, 2, 5]);
+        assert_eq!(p.force_write_element(&mut c, 6), Err(6));
+        drop(observer);
+        assert_eq!(p.force_write_element(&mut c, 6), Ok(None));
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), [6]);
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(2);
+        let mut observer = p.observer().unwrap();
+        assert!(p.write_exact(&[1, 2]));
+        // The oldest element is kept while the observer holds its space.
+        assert_eq!(p.force_write_element(&mut c, 3), Err(3));
+        assert_eq!(c.available(), 2);
+        assert_eq!(observer.advance(1), 1);
+        assert_eq!(p.force_write_element(&mut c, 3), Ok(Some(1)));
+        drop(observer);
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn test_overwriting_buffer() {
+        let (mut p, mut c) = RingBufferBuilder::<u32>::new()
+            .capacity(3)
+            .build_overwriting();
+        assert!(p.observer().is_none());
+        for value in 0..5 {
+            assert!(p.write_element(value));
+        }
+        assert_eq!(p.available(), 0);
+        assert!(p.write_exact(&[5, 6]));
+        assert!(!p.write_exact(&[0; 4]));
+        assert_eq!(c.available(), 3);
+        assert_eq!(c.read_element(), Some(4));
+        assert_eq!(c.dropped(), 4);
+        assert_eq!(p.try_write_element(7), Ok(()));
+        assert_eq!(p.write_from_slice(&[8, 9, 10, 11]), 3);
+        let mut dst = [0; 4];
+        assert_eq!(c.read_into_slice(&mut dst), 3);
+        assert_eq!(&dst[..3], &[9, 10, 11]);
+        assert_eq!(c.dropped(), 7);
+        assert_eq!(c.total_read(), 4);
+        assert!(c.is_empty());
+
+        // The positions wrap around the range of the index type.
+        let (mut p, mut c) = RingBufferBuilder::<u16>::new()
+            .index::<u16>()
+            .capacity(5)
+            .build_overwriting();
+        for value in 0..70_000u32 {
+            p.write_element(value as u16);
+            if value % 7 == 0 {
+                assert_eq!(c.read_element(), Some(value.saturating_sub(4) as u16));
+            }
+        }
+        let mut dst = [0; 5];
+        assert_eq!(c.read_into_slice(&mut dst), 5);
+        assert!(dst.iter().map(|&v| v as u32 + 65_536).eq(69_995..70_000));
+        assert_eq!(c.total_read() + c.dropped(), 70_000);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    #[should_panic(expected = "an overwriting ring buffer cannot be zeroized")]
+    fn test_overwriting_buffer_zeroize() {
+        let _ = RingBufferBuilder::<u8>::new()
+            .capacity(3)
+            .zeroize()
+            .build_overwriting();
+    }
+
+    #[test]
+    fn test_resync_to_newest() {
+        let (mut p, mut c) = create_ring_buffer::<String>(4);
+        assert_eq!(c.resync_to_newest(0), 0);
+        assert_eq!(c.read_latest(), None);
+        for value in ["a", "b", "c"] {
+            assert!(p.write_element(value.to_string()));
+        }
+        assert_eq!(c.advance(2), 2);
+        // Wraps around the end of the buffer.
+        for value in ["d", "e", "f"] {
+            assert!(p.write_element(value.to_string()));
+        }
+        assert_eq!(c.resync_to_newest(10), 0);
+        assert_eq!(c.resync_to_newest(2), 2);
+        assert_eq!(c.total_read(), 4);
+        assert_eq!(c.read_latest().as_deref(), Some("f"));
+        assert!(c.is_empty());
+        assert!(p.write_element("g".to_string()));
+        assert_eq!(c.resync_to_newest(0), 1);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn test_contiguous_available() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!((p.contiguous_available(), c.contiguous_available()), (5, 0));
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!((p.contiguous_available(), c.contiguous_available()), (2, 3));
+        assert_eq!(c.advance(2), 2);
+        assert_eq!((p.contiguous_available(), c.contiguous_available()), (2, 1));
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[4, 5, 6]));
+        assert_eq!((p.contiguous_available(), c.contiguous_available()), (1, 3));
+        assert_eq!(c.advance(3), 3);
+        assert_eq!((p.contiguous_available(), c.contiguous_available()), (4, 1));
+    }
+
+    #[test]
+    fn test_fill_ratio() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!((p.fill_ratio(), c.fill_ratio()), (0.0, 0.0));
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!((p.fill_ratio(), c.fill_ratio()), (0.75, 0.75));
+        assert!(p.write_element(4));
+        assert_eq!((p.fill_ratio(), c.fill_ratio()), (1.0, 1.0));
+        assert_eq!(c.advance(2), 2);
+        assert_eq!((p.fill_ratio(), c.fill_ratio()), (0.5, 0.5));
+
+        let (p, c) = create_ring_buffer::<u8>(0);
+        assert_eq!((p.fill_ratio(), c.fill_ratio()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_total_written_read() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!((p.total_written(), p.total_read()), (0, 0));
+        for _ in 0..4 {
+            assert!(p.write_exact(&[1, 2]));
+            assert!(p.write_element(3));
+            assert_eq!(c.read_slices(|data, _| data.len(), None), 3);
+        }
+        assert!(p.write_exact(&[1, 2]));
+        assert_eq!((p.total_written(), p.total_read()), (14, 12));
+        assert_eq!((c.total_written(), c.total_read()), (14, 12));
+        assert_eq!(p.unwrite(&mut c, 1), 1);
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!((p.total_written(), p.total_read()), (13, 13));
+        assert_eq!((c.total_written(), c.total_read()), (13, 13));
+    }
+
+    #[test]
+    fn test_shared_handles() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(1), 1);
+        let (p, c) = (&p, &c);
+        thread::scope(|s| {
+            s.spawn(move || {
+                assert_eq!((p.available(), p.total_written()), (2, 3));
+                assert_eq!((c.available(), c.peek_element()), (2, Some(&2)));
+            });
+        });
+    }
+
+    #[test]
+    fn test_debug() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(1), 1);
+        assert_eq!(
+            format!("{:?}", p),
+            "Producer { capacity: 4, index: 3, available: 2 }"
+        );
+        assert_eq!(
+            format!("{:?}", c),
+            "Consumer { capacity: 4, index: 1, available: 2 }"
+        );
+    }
+
     #[test]
     fn test_empty_read() {
         let (_p, mut c) = create_ring_buffer::<u8>(10);
@@ -36,26 +770,730 @@ mod tests {
         assert_eq!(p.write_slices(|_data, _offset| 0, None), 0);
     }
 
-    #[test]
-    fn test_full_write_and_read() {
-        let (mut p, mut c) = create_ring_buffer::<u8>(10);
-        assert_eq!(p.write_slices(|data, _offset| data.len(), None), 10);
-        assert_eq!(p.available(), 0);
-        assert_eq!(c.available(), 10);
-        assert_eq!(c.read_slices(|data, _offset| data.len(), None), 10);
-        assert_eq!(p.available(), 10);
-        assert_eq!(c.available(), 0);
+    #[test]
+    fn test_full_write_and_read() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(10);
+        assert_eq!(p.write_slices(|data, _offset| data.len(), None), 10);
+        assert_eq!(p.available(), 0);
+        assert_eq!(c.available(), 10);
+        assert_eq!(c.read_slices(|data, _offset| data.len(), None), 10);
+        assert_eq!(p.available(), 10);
+        assert_eq!(c.available(), 0);
+    }
+
+    #[test]
+    fn test_write_grant() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert!(p.grant(0).is_none());
+        let mut grant = p.grant(3).unwrap();
+        assert_eq!(grant.len(), 3);
+        grant.copy_from_slice(&[1, 2, 3]);
+        assert_eq!(c.available(), 0);
+        assert_eq!(grant.commit(10), 3);
+        assert_eq!(c.available(), 3);
+
+        // The grant ends at the end of the buffer.
+        {
+            // Dropping the grant without committing writes nothing.
+            let mut grant = p.grant(5).unwrap();
+            assert_eq!(grant.len(), 2);
+            grant[0] = 4;
+        }
+        assert_eq!(c.available(), 3);
+        let mut grant = p.grant(5).unwrap();
+        grant.copy_from_slice(&[4, 5]);
+        assert_eq!(grant.commit(2), 2);
+        assert!(p.grant(1).is_none());
+        assert_eq!(c.read_array::<5>(), Some([1, 2, 3, 4, 5]));
+        assert_eq!(p.grant(5).map(|g| g.len()), Some(5));
+    }
+
+    #[test]
+    fn test_read_grant() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert!(c.read_grant(5).is_none());
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert!(c.read_grant(0).is_none());
+        let grant = c.read_grant(3).unwrap();
+        assert_eq!(&*grant, &[1, 2, 3]);
+        assert_eq!(grant.release(10), 3);
+        assert_eq!(p.available(), 4);
+
+        // The grant ends at the end of the buffer.
+        assert!(p.write_exact(&[5, 6, 7]));
+        {
+            // Dropping the grant without releasing consumes nothing.
+            let grant = c.read_grant(5).unwrap();
+            assert_eq!(&*grant, &[4, 5]);
+        }
+        assert_eq!(c.available(), 4);
+        let grant = c.read_grant(5).unwrap();
+        assert_eq!(grant.release(2), 2);
+        assert_eq!(&*c.read_grant(5).unwrap(), &[6, 7]);
+    }
+
+    #[test]
+    fn test_unwrite() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!(p.unwrite(&mut c, 1), 0);
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(p.unwrite(&mut c, 1), 1);
+        assert_eq!(c.available(), 2);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[5, 6, 7]));
+        assert_eq!(p.unwrite(&mut c, 2), 2);
+        assert!(p.write_exact(&[8, 9]));
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![2, 3, 5, 8, 9]);
+        // Elements already read cannot be retracted.
+        assert_eq!(p.unwrite(&mut c, 10), 0);
+        assert!(p.write_element(10));
+        assert_eq!(p.unwrite(&mut c, 10), 1);
+        assert_eq!(c.read_element(), None);
+    }
+
+    #[test]
+    fn test_cached_positions() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.read_element(), Some(1));
+        // The write position cached by the consumer is retracted.
+        assert_eq!(p.unwrite(&mut c, 2), 2);
+        assert_eq!(c.read_element(), None);
+        assert!(p.write_exact(&[4, 5, 6]));
+        assert!(p.write_element(7));
+        assert!(!p.write_element(8));
+        // The producer sees the space freed since it loaded the read position.
+        assert_eq!(c.read_element(), Some(4));
+        assert!(p.write_element(8));
+        assert_eq!(c.read_array::<3>(), Some([5, 6, 7]));
+        assert!(p.reset(&mut c));
+        assert_eq!(c.read_element(), None);
+        assert!(p.write_exact(&[9, 10, 11, 12]));
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![9, 10, 11, 12]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unwrite_other_consumer() {
+        let (mut p, _c) = create_ring_buffer::<u8>(5);
+        let (_, mut other) = create_ring_buffer::<u8>(5);
+        p.unwrite(&mut other, 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        let o = p.observer().unwrap();
+        assert!(!p.reset(&mut c));
+        assert_eq!(c.available(), 1);
+        drop(o);
+        assert!(p.reset(&mut c));
+        assert_eq!((p.available(), c.available()), (4, 0));
+        assert_eq!((p.total_written(), c.total_read()), (0, 0));
+        assert_eq!(p.writable_regions().0, Region { offset: 0, len: 4 });
+        assert!(p.write_exact(&[4, 5, 6, 7]));
+        assert_eq!(c.read_array::<4>(), Some([4, 5, 6, 7]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reset_other_consumer() {
+        let (mut p, _c) = create_ring_buffer::<u8>(5);
+        let (_, mut other) = create_ring_buffer::<u8>(5);
+        p.reset(&mut other);
+    }
+
+    #[test]
+    fn test_reunite() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        assert!(p.write_exact(&[4, 5]));
+        let o = p.observer().unwrap();
+        let (p, c) = match p.reunite(c) {
+            Err(ReuniteError(p, c)) => (p, c),
+            Ok(_) => panic!("reunited while an observer exists"),
+        };
+        drop(o);
+        let (other_p, other_c) = create_ring_buffer::<u8>(4);
+        let (p, c) = match p.reunite(other_c) {
+            Err(ReuniteError(p, other_c)) => {
+                drop((other_p, other_c));
+                (p, c)
+            }
+            Ok(_) => panic!("reunited handles of different ring buffers"),
+        };
+        let unread = p.reunite(c).unwrap();
+        assert_eq!(unread, vec![3, 4, 5]);
+        assert!(unread.capacity() >= 4);
+    }
+
+    #[test]
+    fn test_observer() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert!(p.write_element(1));
+        let mut o = p.observer().unwrap();
+        assert!(p.observer().is_none());
+        assert!(p.write_exact(&[2, 3]));
+        assert_eq!(o.available(), 2);
+        assert_eq!(o.peek_element(), Some(&2));
+        assert_eq!(o.read_element(), Some(2));
+        // The observer does not free space.
+        assert_eq!(c.available(), 3);
+        assert_eq!(p.available(), 1);
+        // Unobserved elements are not overwritten.
+        assert_eq!(c.read_slices(|data, _| data.len(), None), 3);
+        assert_eq!(p.available(), 3);
+        assert!(p.write_exact(&[4, 5, 6]));
+        assert!(!p.write_element(7));
+        assert_eq!(p.unwrite(&mut c, 1), 0);
+        assert_eq!(o.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!(o.advance(2), 2);
+        assert_eq!(p.available(), 1);
+        assert_eq!(o.read_element(), Some(5));
+        drop(o);
+        assert_eq!(p.available(), 1);
+        assert!(p.observer().is_some());
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_element_write() {
+        let (mut p, _) = create_ring_buffer::<u8>(5);
+        assert!(p.write_element(0));
+        assert!(p.write_element(1));
+        assert!(p.write_element(2));
+        assert!(p.write_element(3));
+        assert!(p.write_element(4));
+        assert!(!p.write_element(5));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_index_width() {
+        let (mut p, mut c) = create_ring_buffer_with_index::<u8, u16>(u16::MAX as usize);
+        for round in 0..3u8 {
+            assert_eq!(p.fill_with(round), u16::MAX as usize);
+            assert!(p.is_full());
+            assert_eq!(c.available(), u16::MAX as usize);
+            assert_eq!(c.advance(100), 100);
+            assert!(p.write_exact(&[round; 100]));
+            assert_eq!(c.read_slices(|data, _| data.len(), None), u16::MAX as usize);
+        }
+
+        let (mut p, mut c) = RingBufferBuilder::<u32>::new()
+            .index::<u32>()
+            .capacity(3)
+            .build();
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+        assert!(p.reset(&mut c));
+        assert_eq!(p.reunite(c).unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity exceeds the maximum capacity of the index type")]
+    fn test_index_width_over_capacity() {
+        create_ring_buffer_with_index::<u8, u16>(u16::MAX as usize + 1);
+    }
+
+    #[test]
+    fn test_power_of_two() {
+        let (mut p, mut c) = RingBufferBuilder::<u8>::new()
+            .capacity(5)
+            .power_of_two()
+            .build();
+        assert_eq!(p.capacity(), 8);
+        for value in 0..20 {
+            assert!(p.write_element(value));
+            assert!(p.write_exact(&[value, value]));
+            assert_eq!(c.read_element(), Some(value));
+            assert_eq!(c.read_array::<2>(), Some([value, value]));
+        }
+        assert_eq!(p.writable_regions().0, Region { offset: 4, len: 4 });
+        let result = RingBufferBuilder::<u8>::new()
+            .index::<u16>()
+            .capacity(40000)
+            .power_of_two()
+            .try_build();
+        assert_eq!(result.unwrap_err(), CreateError::CapacityOverflow);
+        let result = RingBufferBuilder::<u8>::new()
+            .capacity(usize::MAX)
+            .power_of_two()
+            .try_build();
+        assert_eq!(result.unwrap_err(), CreateError::CapacityOverflow);
+    }
+
+    #[test]
+    fn test_align() {
+        let (mut p, mut c) = RingBufferBuilder::<String>::new()
+            .capacity(3)
+            .align(256)
+            .build();
+        assert_eq!(p.as_mut_ptr() as usize % 256, 0);
+        assert_eq!(c.as_ptr() as usize % 256, 0);
+        assert!(p.write_element("a".to_string()));
+        assert!(p.write_element("b".to_string()));
+        assert_eq!(c.read_element().as_deref(), Some("a"));
+        // Wraps around the end of the buffer.
+        assert!(p.write_element("c".to_string()));
+        assert!(p.write_element("d".to_string()));
+        let unread = p.reunite(c).unwrap();
+        assert_eq!(unread, ["b", "c", "d"]);
+        assert!(unread.capacity() >= 3);
+
+        // Unread elements are dropped with the ring buffer.
+        let (mut p, c) = RingBufferBuilder::<String>::new()
+            .capacity(2)
+            .align(64)
+            .try_build()
+            .unwrap();
+        assert!(p.write_element("e".to_string()));
+        drop((p, c));
+
+        let (mut p, _c) = RingBufferBuilder::<u8>::new()
+            .capacity(8)
+            .align(4096)
+            .zeroed()
+            .build();
+        assert_eq!(p.as_mut_ptr() as usize % 4096, 0);
+        let (first, _) = p.vacant_slices_uninit();
+        assert!(first.iter().all(|e| unsafe { e.assume_init() } == 0));
+        let (p, _c) = RingBufferBuilder::<u8>::new().align(64).build();
+        assert_eq!(p.capacity(), 0);
+    }
+
+    #[cfg(feature = "huge-pages")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_huge_pages() {
+        let (mut p, mut c) = RingBufferBuilder::<u32>::new()
+            .capacity(1 << 20)
+            .huge_pages()
+            .build();
+        assert_eq!(p.as_mut_ptr() as usize % (2 << 20), 0);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+    }
+
+    #[cfg(all(feature = "numa", target_os = "linux"))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_numa_node() {
+        let (mut p, mut c) = RingBufferBuilder::<u32>::new()
+            .capacity(1 << 16)
+            .numa_node(0)
+            .build();
+        assert_eq!(p.as_mut_ptr() as usize % 4096, 0);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+
+        // A node that does not exist leaves the placement to the kernel.
+        let (mut p, mut c) = RingBufferBuilder::<u8>::new()
+            .capacity(100)
+            .numa_node(1000)
+            .build();
+        assert!(p.write_exact(&[4, 5]));
+        assert_eq!(c.read_array::<2>(), Some([4, 5]));
+    }
+
+    #[cfg(all(feature = "double-mapped", target_os = "linux"))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_double_mapped() {
+        let (mut p, mut c) = RingBufferBuilder::<[u8; 3]>::new()
+            .capacity(10)
+            .align(64)
+            .double_mapped()
+            .build();
+        let capacity = p.capacity();
+        assert_eq!(capacity * 3 % 4096, 0);
+        assert_eq!(p.as_mut_ptr() as usize % 4096, 0);
+        assert_eq!(p.fill_with([0; 3]), capacity);
+        assert_eq!(c.advance(capacity - 2), capacity - 2);
+
+        // The writable elements wrap around the end in a single slice.
+        let mut calls = 0;
+        let written = p.write_slices(
+            |slice, offset| {
+                calls += 1;
+                for (i, element) in slice.iter_mut().enumerate() {
+                    *element = [(offset + i) as u8; 3];
+                }
+                slice.len()
+            },
+            Some(5),
+        );
+        assert_eq!((written, calls), (5, 1));
+        let (first, second) = c.as_slices();
+        assert_eq!(
+            first,
+            &[[0; 3], [0; 3], [0; 3], [1; 3], [2; 3], [3; 3], [4; 3]]
+        );
+        assert!(second.is_empty());
+        assert_eq!(
+            c.readable_regions(),
+            (
+                Region {
+                    offset: capacity - 2,
+                    len: 7
+                },
+                Region { offset: 0, len: 0 }
+            )
+        );
+        assert_eq!(c.advance(2), 2);
+        assert_eq!(
+            p.reunite(c).unwrap(),
+            vec![[0; 3], [1; 3], [2; 3], [3; 3], [4; 3]]
+        );
+
+        let (mut p, mut c) = RingBufferBuilder::<u64>::new()
+            .capacity(1000)
+            .double_mapped()
+            .build();
+        assert_eq!(p.capacity(), 1024);
+        let src: Vec<u64> = (0..1000).collect();
+        for _ in 0..10 {
+            assert!(p.write_exact(&src));
+            let mut dst = vec![0; 1000];
+            assert!(c.read_exact_into(&mut dst));
+            assert_eq!(dst, src);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_align_not_power_of_two() {
+        RingBufferBuilder::<u8>::new().align(48);
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        // Wraps around the end of the buffer.
+        assert_eq!(p.fill_with(0), 4);
+        assert_eq!(p.fill_with(9), 0);
+        assert_eq!(c.advance(3), 3);
+        let mut next = 10;
+        assert_eq!(
+            p.fill_with_fn(|| {
+                next += 1;
+                next
+            }),
+            3
+        );
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![0, 0, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_element_try_write() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(2);
+        assert_eq!(p.try_write_element(1), Ok(()));
+        assert_eq!(p.try_write_element(2), Ok(()));
+        assert_eq!(p.try_write_element(3), Err(3));
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(p.try_write_element(3), Ok(()));
+        assert_eq!(c.read_element(), Some(2));
+        assert_eq!(c.read_element(), Some(3));
+        assert_eq!(c.read_element(), None);
+    }
+
+    #[test]
+    fn test_element_write_ref() {
+        use std::cell::Cell;
+
+        #[derive(Copy)]
+        struct Counted<'a>(&'a Cell<usize>);
+
+        #[allow(clippy::non_canonical_clone_impl)]
+        impl Clone for Counted<'_> {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                *self
+            }
+        }
+
+        let clones = Cell::new(0);
+        let value = Counted(&clones);
+        let (mut p, mut c) = create_ring_buffer::<Counted>(2);
+        assert!(p.write_element_ref(&value));
+        assert!(p.write_element_ref(&value));
+        assert_eq!(clones.get(), 2);
+        assert!(!p.write_element_ref(&value));
+        assert_eq!(clones.get(), 2);
+        assert!(c.read_element().is_some());
+        assert!(p.write_element_ref(&value));
+        assert_eq!(clones.get(), 3);
+        assert_eq!(c.available(), 2);
+    }
+
+    #[test]
+    fn test_non_copy_elements() {
+        use std::rc::Rc;
+
+        let value = Rc::new(());
+        let (mut p, mut c) = create_ring_buffer::<Rc<()>>(4);
+        for _ in 0..3 {
+            assert!(p.write_element(Rc::clone(&value)));
+        }
+        assert_eq!(Rc::strong_count(&value), 4);
+        drop(c.read_element());
+        assert_eq!(Rc::strong_count(&value), 3);
+        // Elements consumed through slices are dropped.
+        assert_eq!(c.read_slices(|data, _| data.len(), Some(1)), 1);
+        assert_eq!(Rc::strong_count(&value), 2);
+        // Wraps around the end of the buffer.
+        assert_eq!(p.fill_with(Rc::clone(&value)), 3);
+        assert_eq!(Rc::strong_count(&value), 5);
+        assert_eq!(p.unwrite(&mut c, 1), 1);
+        assert_eq!(c.advance(1), 1);
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(p);
+        assert_eq!(Rc::strong_count(&value), 3);
+        // The unread elements are dropped with the ring buffer.
+        drop(c);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let (mut p, mut c) = create_ring_buffer::<String>(3);
+        assert!(p.write_element("a".to_string()));
+        assert!(p.write_element("b".to_string()));
+        assert!(p.reset(&mut c));
+        assert!(p.write_element("c".to_string()));
+        assert_eq!(p.reunite(c).unwrap(), vec!["c".to_string()]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use std::{cell::Cell, rc::Rc};
+
+        let (mut p, mut c) = RingBufferBuilder::<u8>::new().capacity(4).zeroize().build();
+        assert!(p.observer().is_none());
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(c.read_slices(|data, _| data.len(), Some(2)), 2);
+        let (first, second) = p.vacant_slices_uninit();
+        assert_eq!((first.len(), second.len()), (3, 0));
+        assert!(first.iter().all(|e| unsafe { e.assume_init() } == 0));
+        // The writable slices are filled with default values again.
+        assert_eq!(p.vacant_slices_mut().0, &[0, 0, 0]);
+
+        struct Secret(Rc<Cell<usize>>);
+
+        impl zeroize::Zeroize for Secret {
+            fn zeroize(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let count = Rc::new(Cell::new(0));
+        let (mut p, mut c) = RingBufferBuilder::<Secret>::new()
+            .capacity(4)
+            .zeroize()
+            .build();
+        for _ in 0..4 {
+            assert!(p.write_element(Secret(Rc::clone(&count))));
+        }
+        assert_eq!(c.advance(1), 1);
+        assert_eq!(count.get(), 1);
+        assert_eq!(p.unwrite(&mut c, 1), 1);
+        assert_eq!(count.get(), 2);
+        // Elements moved out are not zeroized.
+        drop(c.read_element());
+        assert_eq!(count.get(), 2);
+        drop(p);
+        drop(c);
+        assert_eq!(count.get(), 3);
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_wrapped_write() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Fill(u8);
+
+        impl Default for Fill {
+            fn default() -> Self {
+                Fill(0xff)
+            }
+        }
+
+        impl zeroize::Zeroize for Fill {
+            fn zeroize(&mut self) {
+                self.0 = 0;
+            }
+        }
+
+        let (mut p, mut c) = RingBufferBuilder::<Fill>::new()
+            .capacity(4)
+            .zeroize()
+            .build();
+        assert!(p.write_exact(&[Fill(1), Fill(2), Fill(3)]));
+        assert_eq!(c.read_slices(|data, _| data.len(), None), 3);
+        // Both the first and the wrapped region are filled with default values.
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!(first, &[Fill(0xff)]);
+        assert_eq!(second, &[Fill(0xff); 3]);
+        let written = p.write_slices(
+            |data, offset| {
+                assert!(data.iter().all(|e| *e == Fill(0xff)));
+                for (i, e) in data.iter_mut().enumerate() {
+                    *e = Fill((offset + i) as u8 + 4);
+                }
+                data.len()
+            },
+            None,
+        );
+        assert_eq!(written, 4);
+        let mut read = Vec::new();
+        c.read_slices(
+            |data, _| {
+                read.extend_from_slice(data);
+                data.len()
+            },
+            None,
+        );
+        assert_eq!(read, [Fill(4), Fill(5), Fill(6), Fill(7)]);
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!(first, &[Fill(0xff)]);
+        assert_eq!(second, &[Fill(0xff); 3]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_commit() {
+        let (mut p, mut c) = RingBufferBuilder::<u8>::new().capacity(4).zeroize().build();
+        p.vacant_slices_mut().0[..2].copy_from_slice(&[7, 8]);
+        assert_eq!(p.commit(2), 2);
+        assert_eq!(c.read_array::<2>(), Some([7, 8]));
+
+        // Only the region up to the end of the buffer is granted.
+        let mut grant = p.grant(4).unwrap();
+        grant.copy_from_slice(&[1, 2]);
+        assert_eq!(grant.commit(2), 2);
+        assert_eq!(c.read_array::<2>(), Some([1, 2]));
+
+        // The scrubbed space is initialized again, but not the space written
+        // since then.
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first.len(), second.len()), (4, 0));
+        first[..3].copy_from_slice(&[3, 4, 5]);
+        assert_eq!(p.commit(1), 1);
+        assert_eq!(p.write_slices(|data, _| data.len(), Some(2)), 2);
+        assert_eq!(c.read_array::<3>(), Some([3, 4, 5]));
+        assert_eq!(p.commit(4), 4);
+        assert_eq!(c.read_array::<4>(), Some([0; 4]));
+    }
+
+    #[cfg(all(feature = "zeroize", feature = "std"))]
+    #[test]
+    fn test_zeroize_io_slices() {
+        use std::io::Read;
+
+        let (mut p, mut c) = RingBufferBuilder::<u8>::new().capacity(4).zeroize().build();
+        let mut reader: &[u8] = &[7, 8];
+        assert_eq!(reader.read_vectored(&mut p.io_slices_mut()).unwrap(), 2);
+        assert_eq!(p.commit(2), 2);
+        assert_eq!(c.read_array::<2>(), Some([7, 8]));
+    }
+
+    #[test]
+    fn test_create_initialized() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let (mut p, mut c) = create_ring_buffer_zeroed::<[u16; 2]>(3);
+        assert_eq!(p.vacant_slices_mut().0, &[[0, 0]; 3]);
+        assert!(p.write_element([1, 2]));
+        assert_eq!(c.read_element(), Some([1, 2]));
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first.len(), second.len()), (2, 1));
+        assert_eq!(first, &[[0, 0]; 2]);
+
+        let (mut p, _c) = create_ring_buffer_default::<Option<char>>(2);
+        assert_eq!(p.vacant_slices_mut().0, &[None, None]);
+
+        static DEFAULTS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Counted(u8);
+
+        impl Default for Counted {
+            fn default() -> Self {
+                DEFAULTS.fetch_add(1, Ordering::Relaxed);
+                Counted(0)
+            }
+        }
+
+        let (mut p, mut c) = create_ring_buffer_default::<Counted>(3);
+        assert_eq!(DEFAULTS.load(Ordering::Relaxed), 3);
+        // The storage is not filled with default values again.
+        for i in 0..5 {
+            assert_eq!(
+                p.write_slices(
+                    |data, _| {
+                        data[0] = Counted(i);
+                        1
+                    },
+                    Some(1),
+                ),
+                1
+            );
+            assert_eq!(c.read_element(), Some(Counted(i)));
+        }
+        assert_eq!(DEFAULTS.load(Ordering::Relaxed), 3);
     }
 
     #[test]
-    fn test_element_write() {
-        let (mut p, _) = create_ring_buffer::<u8>(5);
-        assert!(p.write_element(0));
+    fn test_uninit_writes() {
+        let (mut p, mut c) = create_ring_buffer::<String>(4);
+        let written = unsafe {
+            p.write_slices_uninit(
+                |data, offset| {
+                    for (i, element) in data.iter_mut().enumerate() {
+                        element.write((offset + i).to_string());
+                    }
+                    data.len()
+                },
+                Some(3),
+            )
+        };
+        assert_eq!(written, 3);
+        assert_eq!(c.advance(2), 2);
+        let (first, second) = p.vacant_slices_uninit();
+        assert_eq!((first.len(), second.len()), (1, 2));
+        first[0].write("x".to_string());
+        second[0].write("y".to_string());
+        unsafe { p.advance(2) };
+        let mut read = Vec::new();
+        while let Some(s) = c.read_element() {
+            read.push(s);
+        }
+        assert_eq!(read, vec!["2", "x", "y"]);
+
+        // Writable slices of `Default` elements are initialized.
+        let (mut p, mut c) = create_ring_buffer::<u32>(4);
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first, second), (&mut [0; 4][..], &mut [][..]));
+        assert_eq!(p.write_slices(|data, _| data.len(), Some(2)), 2);
+        assert_eq!(c.read_array::<2>(), Some([0, 0]));
+
+        // Space handed out as `MaybeUninit<T>` is initialized again.
+        let (mut p, mut c) = create_ring_buffer_default::<u32>(4);
         assert!(p.write_element(1));
-        assert!(p.write_element(2));
-        assert!(p.write_element(3));
-        assert!(p.write_element(4));
-        assert!(!p.write_element(5));
+        assert_eq!(c.read_element(), Some(1));
+        p.vacant_slices_mut().0.fill(7);
+        let (first, second) = p.vacant_slices_uninit();
+        assert_eq!((first.len(), second.len()), (3, 1));
+        first.fill(MaybeUninit::uninit());
+        second.fill(MaybeUninit::uninit());
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first, second), (&mut [0; 3][..], &mut [0][..]));
     }
 
     #[test]
@@ -75,6 +1513,39 @@ mod tests {
         assert_eq!(c.read_element(), None);
     }
 
+    #[test]
+    fn test_peek_element() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        assert_eq!(c.peek_element(), None);
+        assert!(p.write_element(1));
+        assert!(p.write_element(2));
+        assert_eq!(c.peek_element(), Some(&1));
+        assert_eq!(c.available(), 2);
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(c.peek_element(), Some(&2));
+        assert!(p.write_element(3));
+        assert!(p.write_element(4));
+        assert_eq!(c.read_element(), Some(2));
+        assert_eq!(c.read_element(), Some(3));
+        assert_eq!(c.peek_element(), Some(&4));
+        assert_eq!(c.read_element(), Some(4));
+        assert_eq!(c.peek_element(), None);
+    }
+
+    #[test]
+    fn test_element_read_without_copy_bound() {
+        // Neither `read_element` nor `pop_iter` require `T: Copy`.
+        fn read_two<T>(c: &mut Consumer<T>) -> (Option<T>, Vec<T>) {
+            (c.read_element(), c.pop_iter().take(1).collect())
+        }
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(read_two(&mut c), (Some(1), vec![2]));
+        assert_eq!(read_two(&mut c), (Some(3), vec![]));
+        assert_eq!(read_two(&mut c), (None, vec![]));
+    }
+
     #[test]
     fn test_element_read_write() {
         let (mut p, mut c) = create_ring_buffer::<u8>(5);
@@ -97,6 +1568,186 @@ mod tests {
         assert_eq!(c.read_element(), None);
     }
 
+    #[test]
+    fn test_pop_iter() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(c.pop_iter().next(), None);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.pop_iter().size_hint(), (3, None));
+        assert_eq!(c.pop_iter().take(2).collect::<Vec<_>>(), vec![1, 2]);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[4, 5, 6]));
+        let mut expected = 3;
+        for value in c.pop_iter() {
+            assert_eq!(value, expected);
+            expected += 1;
+        }
+        assert_eq!(expected, 7);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.available(), 4);
+    }
+
+    #[test]
+    fn test_drain() {
+        use std::rc::Rc;
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(c.drain().next(), None);
+        assert!(p.write_exact(&[1, 2, 3]));
+        let mut drain = c.drain();
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next(), Some(1));
+        assert!(p.write_element(4));
+        assert_eq!(drain.len(), 2);
+        assert_eq!(drain.collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(c.available(), 1);
+
+        // Wraps around the end of the buffer, and drops the remaining elements.
+        assert!(p.write_exact(&[5, 6]));
+        let mut drain = c.drain();
+        assert_eq!(drain.next(), Some(4));
+        drop(drain);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.available(), 4);
+        assert!(p.write_element(7));
+        assert_eq!(c.read_element(), Some(7));
+
+        // Elements that are not `Copy` are moved out or dropped.
+        let value = Rc::new(());
+        let (mut p, mut c) = create_ring_buffer::<Rc<()>>(3);
+        for _ in 0..3 {
+            assert!(p.write_element(Rc::clone(&value)));
+        }
+        let mut drain = c.drain();
+        drop(drain.next());
+        drop(drain);
+        assert_eq!(c.available(), 0);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_iter() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(c.iter().next(), None);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(c.read_element(), Some(1));
+        assert_eq!(c.read_element(), Some(2));
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[4, 5, 6]));
+        assert_eq!(c.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+        assert_eq!(c.available(), 4);
+        assert_eq!(p.available(), 0);
+    }
+
+    #[test]
+    fn test_as_slices_advance() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!(c.as_slices(), (&[][..], &[][..]));
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert_eq!(c.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+        assert_eq!(c.advance(3), 3);
+        assert_eq!(c.as_slices(), (&[4][..], &[][..]));
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[5, 6, 7]));
+        assert_eq!(c.as_slices(), (&[4, 5][..], &[6, 7][..]));
+        assert_eq!(c.advance(3), 3);
+        assert_eq!(c.as_slices(), (&[7][..], &[][..]));
+        assert_eq!(c.advance(10), 1);
+        assert_eq!(c.advance(1), 0);
+        assert_eq!(p.available(), 5);
+    }
+
+    #[test]
+    fn test_vacant_slices_mut_commit() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first.len(), second.len()), (5, 0));
+        first[..4].copy_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.commit(4), 4);
+        assert_eq!(c.available(), 4);
+        assert_eq!(c.advance(3), 3);
+        // Wraps around the end of the buffer.
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first.len(), second.len()), (1, 3));
+        first.copy_from_slice(&[5]);
+        second.copy_from_slice(&[6, 7, 8]);
+        assert_eq!(p.commit(10), 4);
+        let (first, second) = p.vacant_slices_mut();
+        assert_eq!((first.len(), second.len()), (0, 0));
+        assert_eq!(c.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_producer_advance() {
+        let (mut p, mut c) = create_ring_buffer::<u32>(4);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        // Simulates an external writer filling the memory through raw pointers.
+        let (first, second) = p.vacant_slices_mut();
+        let (first, second) = (first.as_mut_ptr(), second.as_mut_ptr());
+        unsafe {
+            first.write(4);
+            second.write(5);
+            second.add(1).write(6);
+            assert_eq!(p.advance(10), 3);
+        }
+        assert_eq!(p.total_written(), 6);
+        assert_eq!(c.pop_iter().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_raw_regions() {
+        let (mut p, mut c) = create_ring_buffer::<u16>(4);
+        assert_eq!(p.as_mut_ptr() as *const u16, c.as_ptr());
+        let empty = Region { offset: 0, len: 0 };
+        assert_eq!(p.writable_regions(), (Region { offset: 0, len: 4 }, empty));
+        assert_eq!(c.readable_regions(), (empty, empty));
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        let (first, second) = p.writable_regions();
+        assert_eq!(
+            (first, second),
+            (Region { offset: 3, len: 1 }, Region { offset: 0, len: 2 })
+        );
+        let base = p.as_mut_ptr();
+        unsafe {
+            base.add(first.offset).write(4);
+            base.add(second.offset).write(5);
+            assert_eq!(p.advance(2), 2);
+        }
+        let (first, second) = c.readable_regions();
+        assert_eq!(
+            (first, second),
+            (Region { offset: 2, len: 2 }, Region { offset: 0, len: 1 })
+        );
+        let base = c.as_ptr();
+        let read = (first.offset..first.offset + first.len)
+            .chain(second.offset..second.offset + second.len)
+            .map(|i| unsafe { base.add(i).read() })
+            .collect::<Vec<_>>();
+        assert_eq!(read, vec![3, 4, 5]);
+        assert_eq!(c.advance(read.len()), 3);
+        assert_eq!(p.available(), 4);
+    }
+
+    #[test]
+    fn test_get() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!(c.get(0), None);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[4, 5, 6]));
+        assert_eq!(c.get(0), Some(&3));
+        assert_eq!(c.get(1), Some(&4));
+        assert_eq!(c.get(2), Some(&5));
+        assert_eq!(c.get(3), Some(&6));
+        assert_eq!(c.get(4), None);
+        assert_eq!(c.available(), 4);
+    }
+
     #[test]
     fn test_write_max_size() {
         let (mut p, mut c) = create_ring_buffer::<u8>(10);
@@ -139,6 +1790,266 @@ mod tests {
         assert_eq!(c.available(), 7);
     }
 
+    #[test]
+    fn test_slices_at_least() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert_eq!(p.write_slices_at_least(9, |_, _| unreachable!(), None), 0);
+        assert_eq!(p.write_slices_at_least(8, |data, _| data.len(), Some(6)), 6);
+        assert_eq!(p.write_slices_at_least(3, |_, _| unreachable!(), None), 0);
+        assert_eq!(c.read_slices_at_least(7, |_, _| unreachable!(), None), 0);
+        assert_eq!(c.read_slices_at_least(4, |data, _| data.len(), Some(4)), 4);
+        assert_eq!(c.read_slices_at_least(4, |_, _| unreachable!(), None), 0);
+        // Wraps around the end of the buffer.
+        assert_eq!(p.write_slices_at_least(6, |data, _| data.len(), None), 6);
+        assert_eq!(c.read_slices_at_least(8, |data, _| data.len(), None), 8);
+        assert_eq!(c.read_slices_at_least(0, |_, _| unreachable!(), None), 0);
+    }
+
+    #[test]
+    fn test_slices_chunked() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(10);
+        assert!(p.write_exact(&[0; 7]));
+        assert_eq!(c.advance(7), 7);
+        // Chunks are also split at the end of the buffer.
+        let mut lens = Vec::new();
+        assert_eq!(
+            p.write_slices_chunked(
+                |data, offset| {
+                    lens.push(data.len());
+                    data.iter_mut()
+                        .enumerate()
+                        .for_each(|(i, v)| *v = (offset + i) as u8);
+                    data.len()
+                },
+                Some(9),
+                4
+            ),
+            9
+        );
+        assert_eq!(lens, vec![3, 4, 2]);
+
+        let mut chunks = Vec::new();
+        assert_eq!(
+            c.read_slices_chunked(
+                |data, _offset| {
+                    chunks.push(data.to_vec());
+                    data.len().min(3)
+                },
+                None,
+                5
+            ),
+            6
+        );
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 4, 5, 6, 7]]);
+        assert_eq!(c.available(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slices_chunked_zero() {
+        let (mut p, _c) = create_ring_buffer::<u8>(10);
+        p.write_slices_chunked(|data, _offset| data.len(), None, 0);
+    }
+
+    #[test]
+    fn test_write_slices_incremental() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(10);
+        assert!(p.write_exact(&[0; 7]));
+        assert_eq!(c.advance(7), 7);
+        let mut offsets = Vec::new();
+        assert_eq!(
+            p.write_slices_incremental(
+                |data, offset| {
+                    assert_eq!(c.available(), offset);
+                    offsets.push(offset);
+                    data.fill(offset as u8);
+                    data.len()
+                },
+                None,
+                4
+            ),
+            10
+        );
+        assert_eq!(offsets, vec![0, 3, 7]);
+        assert_eq!(c.available(), 10);
+
+        // Only the elements actually written are published.
+        assert_eq!(c.advance(5), 5);
+        assert_eq!(
+            p.write_slices_incremental(|data, _offset| data.len() - 1, None, usize::MAX),
+            2
+        );
+        assert_eq!(c.available(), 7);
+        assert_eq!(p.available(), 3);
+    }
+
+    #[test]
+    fn test_slices_until() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(&[0; 6]));
+        assert_eq!(c.advance(6), 6);
+
+        // The closure breaks in the second (wrapped-around) slice.
+        let result = p.write_slices_until(
+            |data, offset| {
+                data.iter_mut()
+                    .enumerate()
+                    .for_each(|(i, v)| *v = (offset + i) as u8);
+                if offset == 0 {
+                    (data.len(), ControlFlow::Continue(()))
+                } else {
+                    (3, ControlFlow::Break(offset))
+                }
+            },
+            None,
+        );
+        assert_eq!(result, (5, Some(2)));
+        assert_eq!(c.available(), 5);
+
+        let result = c.read_slices_until(
+            |data, offset| match data.iter().position(|&v| v == 3) {
+                Some(pos) => (pos + 1, ControlFlow::Break(offset + pos)),
+                None => (data.len(), ControlFlow::Continue(())),
+            },
+            None,
+        );
+        assert_eq!(result, (4, Some(3)));
+        assert_eq!(c.available(), 1);
+
+        // Without breaking, the result is `None`.
+        let result = c.read_slices_until::<()>(
+            |data, _offset| (data.len(), ControlFlow::Continue(())),
+            None,
+        );
+        assert_eq!(result, (1, None));
+        assert_eq!(
+            p.write_slices_until(|_, _| (0, ControlFlow::Break(())), None),
+            (0, Some(()))
+        );
+    }
+
+    #[test]
+    fn test_slices_result() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(&[0; 6]));
+        assert_eq!(c.advance(6), 6);
+
+        // The closure fails in the second (wrapped-around) slice.
+        let result = p.write_slices_result(
+            |data, offset| {
+                if offset == 0 {
+                    data.fill(1);
+                    Ok(data.len())
+                } else {
+                    Err("write error")
+                }
+            },
+            None,
+        );
+        assert_eq!(result, Err((2, "write error")));
+        assert_eq!(c.available(), 2);
+        assert_eq!(
+            p.write_slices_result::<()>(|data, _| Ok(data.len()), Some(3)),
+            Ok(3)
+        );
+        assert_eq!(c.available(), 5);
+
+        let result = c.read_slices_result(
+            |data, offset| {
+                if offset == 0 {
+                    Ok(data.len())
+                } else {
+                    Err(offset)
+                }
+            },
+            None,
+        );
+        assert_eq!(result, Err((2, 2)));
+        assert_eq!(c.available(), 3);
+        assert_eq!(
+            c.read_slices_result::<()>(|data, _| Ok(data.len()), None),
+            Ok(3)
+        );
+        assert_eq!(c.available(), 0);
+    }
+
+    #[test]
+    fn test_write_slices_panic() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(&[0; 6]));
+        assert_eq!(c.advance(6), 6);
+
+        // The closure panics in the second (wrapped-around) slice.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            p.write_slices(
+                |data, offset| {
+                    if offset != 0 {
+                        panic!("write failure");
+                    }
+                    data.copy_from_slice(&[1, 2]);
+                    data.len()
+                },
+                None,
+            )
+        }));
+        assert!(result.is_err());
+        assert_eq!(c.available(), 2);
+        assert_eq!(p.available(), 6);
+        assert!(p.write_exact(&[3, 4, 5, 6, 7, 8]));
+        let mut buf = [0u8; 8];
+        assert!(c.read_exact_into(&mut buf));
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the closure returned 3, but the slice has only 2 elements")]
+    fn test_slices_overrun() {
+        let (mut p, _c) = create_ring_buffer::<u8>(2);
+        p.write_slices(|data, _| data.len() + 1, None);
+    }
+
+    #[test]
+    fn test_read_slices_overrun() {
+        let (mut p, mut c) = create_ring_buffer::<String>(4);
+        p.write_element("a".into());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            c.read_slices(|data, _| data.len() + 1, None);
+        }));
+        assert!(result.is_err());
+        // Nothing was consumed, so the element is still readable.
+        assert_eq!(c.available(), 1);
+        assert_eq!(c.read_element().as_deref(), Some("a"));
+        assert_eq!(p.available(), 4);
+    }
+
+    #[test]
+    fn test_read_slices_panic() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(8);
+        assert!(p.write_exact(&[0; 6]));
+        assert_eq!(c.advance(6), 6);
+        assert!(p.write_exact(&[1, 2, 3, 4, 5]));
+
+        // The closure panics in the second (wrapped-around) slice.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            c.read_slices(
+                |data, offset| {
+                    if offset != 0 {
+                        panic!("read failure");
+                    }
+                    assert_eq!(data, &[1, 2]);
+                    data.len()
+                },
+                None,
+            )
+        }));
+        assert!(result.is_err());
+        assert_eq!(c.available(), 3);
+        assert_eq!(p.available(), 5);
+        let mut buf = [0u8; 3];
+        assert!(c.read_exact_into(&mut buf));
+        assert_eq!(buf, [3, 4, 5]);
+    }
+
     #[test]
     fn test_wraparound() {
         let (mut p, mut c) = create_ring_buffer::<u8>(10);
@@ -327,63 +2238,233 @@ mod tests {
     #[test]
     fn test_readme_example() {
         let (mut producer, mut consumer) = create_ring_buffer::<u8>(5);
-        
+
         // Write data to the buffer in slices
-        producer.write_slices(|data, _offset| {
-            data[..3].copy_from_slice(&[1, 2, 3]);
-            3
-        }, None);
+        producer.write_slices(
+            |data, _offset| {
+                data[..3].copy_from_slice(&[1, 2, 3]);
+                3
+            },
+            None,
+        );
 
-        producer.write_slices(|data, _offset| {
-            data[..1].copy_from_slice(&[4]);
-            1
-        }, None);
+        producer.write_slices(
+            |data, _offset| {
+                data[..1].copy_from_slice(&[4]);
+                1
+            },
+            None,
+        );
 
         // Read the data
-        consumer.read_slices(|data, _offset| {
-            assert_eq!(&data[..4], &[1, 2, 3, 4]);
-            4
-        }, None);
+        consumer.read_slices(
+            |data, _offset| {
+                assert_eq!(&data[..4], &[1, 2, 3, 4]);
+                4
+            },
+            None,
+        );
 
         // Test wrap-around by writing more data
-        producer.write_slices(|data, offset| {
-            if offset == 0 {
-                data[..1].copy_from_slice(&[6]);
-                1
-            } else if offset == 1 {
-                data[..1].copy_from_slice(&[7]);
-                1
-            } else {
-                panic!("Unexpected offset: {}", offset);
-            }
-        }, None);
+        producer.write_slices(
+            |data, offset| {
+                if offset == 0 {
+                    data[..1].copy_from_slice(&[6]);
+                    1
+                } else if offset == 1 {
+                    data[..1].copy_from_slice(&[7]);
+                    1
+                } else {
+                    panic!("Unexpected offset: {}", offset);
+                }
+            },
+            None,
+        );
 
         // Verify the wrap-around data
-        consumer.read_slices(|data, offset| {
-            if offset == 0 {
-                assert_eq!(data, &[6]);  // Read the last part of the buffer
-            } else if offset == 1 {
-                assert_eq!(data, &[7]);  // Read the wrapped-around part
-            } else {
-                panic!("Unexpected offset: {}", offset);  // Ensure only 0 or 1 is valid
-            }
-            data.len()
-        }, None);
+        consumer.read_slices(
+            |data, offset| {
+                if offset == 0 {
+                    assert_eq!(data, &[6]); // Read the last part of the buffer
+                } else if offset == 1 {
+                    assert_eq!(data, &[7]); // Read the wrapped-around part
+                } else {
+                    panic!("Unexpected offset: {}", offset); // Ensure only 0 or 1 is valid
+                }
+                data.len()
+            },
+            None,
+        );
 
         // Write 5 more values to test wrap-around in one go
         let test_data = [8, 9, 10, 11, 12];
-        producer.write_slices(|data, offset| {
-            let write_len = data.len().min(test_data.len() - offset);
-            data[..write_len].copy_from_slice(&test_data[offset..offset + write_len]);
-            write_len
-        }, None);
+        producer.write_slices(
+            |data, offset| {
+                let write_len = data.len().min(test_data.len() - offset);
+                data[..write_len].copy_from_slice(&test_data[offset..offset + write_len]);
+                write_len
+            },
+            None,
+        );
+
+        // Read the newly written values to verify wrap-around
+        consumer.read_slices(
+            |data, offset| {
+                let read_len = data.len().min(test_data.len() - offset);
+                assert_eq!(&data[..read_len], &test_data[offset..offset + read_len]);
+                read_len
+            },
+            None,
+        );
+    }
+
+    #[test]
+    fn test_read_into_slice() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        let mut buf = [0u8; 4];
+        assert_eq!(c.read_into_slice(&mut buf), 0);
+        for value in 1..=4 {
+            assert!(p.write_element(value));
+        }
+        assert_eq!(c.read_into_slice(&mut buf[..3]), 3);
+        assert_eq!(buf, [1, 2, 3, 0]);
+        for value in 5..=8 {
+            assert!(p.write_element(value));
+        }
+        // Wraps around the end of the buffer.
+        assert_eq!(c.read_into_slice(&mut buf), 4);
+        assert_eq!(buf, [4, 5, 6, 7]);
+        assert_eq!(c.read_into_slice(&mut buf), 1);
+        assert_eq!(buf, [8, 5, 6, 7]);
+        assert_eq!(c.available(), 0);
+        assert_eq!(p.available(), 5);
+    }
+
+    #[test]
+    fn test_write_exact() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert!(!p.write_exact(&[4, 5, 6]));
+        assert_eq!(p.available(), 2);
+        assert_eq!(c.available(), 3);
+        assert_eq!(c.read_slices(|data, _offset| data.len(), Some(2)), 2);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[4, 5, 6, 7]));
+        assert!(!p.write_exact(&[8]));
+        assert!(p.write_exact(&[]));
+        let mut buf = [0u8; 5];
+        assert_eq!(c.read_into_slice(&mut buf), 5);
+        assert_eq!(buf, [3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_available_hint() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        assert_eq!((p.available_hint(), c.available_hint()), (4, 0));
+        let o = p.observer().unwrap();
+        assert!(p.write_exact(&[1, 2, 3]));
+        assert_eq!(c.advance(2), 2);
+        // The elements the observer has not read are not writable.
+        assert_eq!((p.available_hint(), c.available_hint()), (1, 1));
+        drop(o);
+        assert_eq!((p.available_hint(), c.available_hint()), (3, 1));
+    }
+
+    #[test]
+    fn test_write_batch() {
+        let (mut p, mut c) = create_ring_buffer::<String>(3);
+        assert!(p.write_element("a".to_string()));
+        {
+            let mut batch = p.batch();
+            assert!(batch.write_element("b".to_string()));
+            assert_eq!(c.read_element().as_deref(), Some("a"));
+            assert!(batch.write_element("c".to_string()));
+            assert_eq!(c.available(), 0);
+            assert_eq!((batch.pending(), batch.flush()), (2, 2));
+            assert_eq!(c.available(), 2);
+            // Wraps around the end of the buffer.
+            assert!(batch.write_element("d".to_string()));
+            assert!(!batch.write_element("e".to_string()));
+            assert_eq!(c.read_element().as_deref(), Some("b"));
+            assert!(batch.write_element("e".to_string()));
+        }
+        assert_eq!(p.total_written(), 5);
+        let read = c.pop_iter().collect::<Vec<_>>();
+        assert_eq!(read, ["c", "d", "e"]);
+
+        // A leaked batch publishes nothing.
+        let (mut p, mut c) = create_ring_buffer::<u8>(3);
+        let mut batch = p.batch();
+        assert!(batch.write_element(1));
+        std::mem::forget(batch);
+        assert_eq!(c.available(), 0);
+        assert!(p.write_exact(&[2, 3, 4]));
+        assert_eq!(c.read_array::<3>(), Some([2, 3, 4]));
+    }
+
+    #[test]
+    fn test_write_from_slice() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!(p.write_from_slice(&[1, 2, 3]), 3);
+        assert_eq!(p.write_from_slice(&[4, 5, 6]), 2);
+        assert_eq!(p.write_from_slice(&[6]), 0);
+        assert_eq!(c.advance(4), 4);
+        // Wraps around the end of the buffer, and continues after it.
+        assert_eq!(p.write_from_slice(&[6, 7, 8]), 3);
+        assert_eq!(p.write_from_slice(&[9]), 1);
+        let mut buf = [0u8; 5];
+        assert_eq!(c.read_into_slice(&mut buf), 5);
+        assert_eq!(buf, [5, 6, 7, 8, 9]);
+        assert_eq!(p.write_from_slice(&[10, 11]), 2);
+        assert_eq!(c.read_into_slice(&mut buf), 2);
+        assert_eq!(buf[..2], [10, 11]);
+        assert_eq!((p.total_written(), c.total_read()), (11, 11));
+    }
+
+    #[test]
+    fn test_read_exact_into() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        let mut buf = [0u8; 3];
+        assert!(!c.read_exact_into(&mut buf));
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert!(c.read_exact_into(&mut buf));
+        assert_eq!(buf, [1, 2, 3]);
+        assert!(!c.read_exact_into(&mut buf));
+        assert_eq!(c.available(), 1);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[5, 6]));
+        assert!(c.read_exact_into(&mut buf));
+        assert_eq!(buf, [4, 5, 6]);
+        assert!(c.read_exact_into(&mut []));
+        assert_eq!(c.available(), 0);
+    }
 
-        // Read the newly written values to verify wrap-around
-        consumer.read_slices(|data, offset| {
-            let read_len = data.len().min(test_data.len() - offset);
-            assert_eq!(&data[..read_len], &test_data[offset..offset + read_len]);
-            read_len
-        }, None);
+    #[test]
+    fn test_read_array() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!(c.read_array::<1>(), None);
+        assert!(p.write_exact(&[1, 2, 3, 4]));
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+        assert_eq!(c.read_array::<2>(), None);
+        // Wraps around the end of the buffer.
+        assert!(p.write_exact(&[5, 6, 7]));
+        assert_eq!(c.read_array::<4>(), Some([4, 5, 6, 7]));
+        assert_eq!(c.read_array::<0>(), Some([]));
+        assert_eq!(c.available(), 0);
+    }
+
+    #[test]
+    fn test_write_array() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(5);
+        assert_eq!(p.write_array([1, 2, 3, 4]), Ok(()));
+        assert_eq!(p.write_array([5, 6]), Err([5, 6]));
+        assert_eq!(p.available(), 1);
+        assert_eq!(c.read_array::<3>(), Some([1, 2, 3]));
+        // Wraps around the end of the buffer.
+        assert_eq!(p.write_array([5, 6, 7, 8]), Ok(()));
+        assert_eq!(p.write_array([]), Ok(()));
+        assert_eq!(p.write_array([9]), Err([9]));
+        assert_eq!(c.read_array::<5>(), Some([4, 5, 6, 7, 8]));
     }
 
     #[test]
@@ -402,7 +2483,7 @@ mod tests {
             vec
         }
 
-        fn write_array<T: Copy>(producer: &mut Producer<T>, buf: &[T]) -> usize {
+        fn write_array<T: Copy + Default>(producer: &mut Producer<T>, buf: &[T]) -> usize {
             producer.write_slices(
                 |dest, offset| {
                     dest.copy_from_slice(&buf[offset..offset + dest.len()]);
@@ -468,6 +2549,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(miri, ignore)]
     fn test_concurrent_element_read_write() {
         const TEST_COUNT: usize = 500_000;
         let (mut p, mut c) = create_ring_buffer::<usize>(10000);
@@ -496,6 +2578,844 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_concurrent_overwriting() {
+        const TEST_COUNT: u64 = 500_000;
+        let (mut p, mut c) = RingBufferBuilder::<[u64; 4]>::new()
+            .capacity(64)
+            .build_overwriting();
+        let p = thread::spawn(move || {
+            for value in 0..TEST_COUNT {
+                // Never waits for the consumer.
+                assert!(p.write_element([value; 4]));
+            }
+        });
+
+        let mut next = 0;
+        let mut received = 0;
+        while !p.is_finished() || !c.is_empty() {
+            if let Some(element) = c.read_element() {
+                // No element is torn or read twice, and the order is kept.
+                assert_eq!(element, [element[0]; 4]);
+                assert!(element[0] >= next);
+                next = element[0] + 1;
+                received += 1;
+            }
+        }
+        p.join().unwrap();
+        assert_eq!(next, TEST_COUNT);
+        assert_eq!(c.total_read(), received);
+        assert_eq!(c.total_read() + c.dropped(), TEST_COUNT);
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_blocking_read_write() {
+        const TEST_COUNT: usize = 500_000;
+        let (mut p, mut c) = create_ring_buffer::<usize>(100);
+        let p = thread::spawn(move || {
+            let src: Vec<usize> = (0..TEST_COUNT).collect();
+            for chunk in src.chunks(37) {
+                assert_eq!(p.write_blocking(chunk), chunk.len());
+            }
+        });
+
+        let mut dst = vec![0; 1000];
+        for i in 0..TEST_COUNT / 1000 {
+            assert_eq!(c.read_blocking(&mut dst), 1000);
+            assert!(dst.iter().copied().eq(i * 1000..(i + 1) * 1000));
+        }
+        p.join().unwrap();
+
+        // Returns early once the producer is dropped and the buffer is drained.
+        assert_eq!(c.read_blocking(&mut dst), 0);
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_blocking_timeout() {
+        use std::time::{Duration, Instant};
+
+        // The consumer stalls, so the producer returns after the timeout.
+        let (mut p, mut c) = create_ring_buffer::<u32>(8);
+        let start = Instant::now();
+        assert_eq!(p.write_timeout(&[7; 20], Duration::from_millis(2)), 8);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(2));
+        assert!(elapsed < Duration::from_secs(1));
+
+        // Space freed before the deadline is used.
+        let consumer = thread::spawn(move || {
+            let mut dst = [0; 12];
+            assert_eq!(c.read_timeout(&mut dst, Duration::from_secs(10)), 12);
+            c
+        });
+        assert_eq!(p.write_timeout(&[8; 12], Duration::from_secs(10)), 12);
+        let mut c = consumer.join().unwrap();
+        assert_eq!(c.read_array::<8>(), Some([8; 8]));
+
+        let mut dst = [0; 4];
+        let start = Instant::now();
+        assert_eq!(c.read_timeout(&mut dst, Duration::from_millis(2)), 0);
+        assert!(start.elapsed() >= Duration::from_millis(2));
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_wait_for_space_and_data() {
+        const TEST_COUNT: usize = 100_000;
+        let (mut p, mut c) = create_ring_buffer::<usize>(64);
+        assert!(!p.wait_for_space(65));
+        assert!(!c.wait_for_data(65));
+        let producer = thread::spawn(move || {
+            for i in 0..TEST_COUNT / 16 {
+                assert!(p.wait_for_space(16));
+                let values: Vec<usize> = (i * 16..(i + 1) * 16).collect();
+                assert!(p.write_exact(&values));
+            }
+        });
+        for i in 0..TEST_COUNT / 10 {
+            assert!(c.wait_for_data(10));
+            let values = c.read_array::<10>().unwrap();
+            assert!(values.iter().copied().eq(i * 10..(i + 1) * 10));
+        }
+        producer.join().unwrap();
+        assert!(!c.wait_for_data(1));
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_wait_for_space_and_data_non_copy() {
+        let (mut p, mut c) = create_ring_buffer::<String>(2);
+        let producer = thread::spawn(move || {
+            for i in 0..100 {
+                assert!(p.wait_for_space(1));
+                assert!(p.write_element(i.to_string()));
+            }
+        });
+        for i in 0..100 {
+            assert!(c.wait_for_data(1));
+            assert_eq!(c.read_element(), Some(i.to_string()));
+        }
+        producer.join().unwrap();
+        assert!(!c.wait_for_data(1));
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_wait_strategies() {
+        use direct_ring_buffer::{Backoff, BusySpin, Park, SpinThenYield, WaitStrategy};
+        use std::time::{Duration, Instant};
+
+        fn transfer(strategy: impl WaitStrategy + 'static) {
+            const TEST_COUNT: usize = 10_000;
+            let (mut p, mut c) = RingBufferBuilder::<usize>::new()
+                .capacity(1000)
+                .wait_strategy(strategy)
+                .build();
+            let producer = thread::spawn(move || {
+                let src: Vec<usize> = (0..TEST_COUNT).collect();
+                assert_eq!(p.write_blocking(&src), TEST_COUNT);
+                p
+            });
+            let mut dst = vec![0; TEST_COUNT];
+            assert_eq!(c.read_blocking(&mut dst), TEST_COUNT);
+            assert!(dst.iter().copied().eq(0..TEST_COUNT));
+            let _p = producer.join().unwrap();
+
+            // The deadline is respected without parking as well.
+            let start = Instant::now();
+            assert_eq!(c.read_timeout(&mut dst, Duration::from_millis(2)), 0);
+            assert!(start.elapsed() >= Duration::from_millis(2));
+        }
+
+        transfer(Park);
+        transfer(BusySpin);
+        transfer(SpinThenYield::default());
+        transfer(Backoff::default());
+        transfer(Backoff {
+            spin_limit: 0,
+            yield_limit: 0,
+        });
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_blocking_peer_dropped() {
+        let (mut p, c) = create_ring_buffer::<u8>(4);
+        let consumer = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drop(c);
+        });
+        assert_eq!(p.write_blocking(&[0; 10]), 4);
+        consumer.join().unwrap();
+
+        let (p, mut c) = create_ring_buffer::<u8>(4);
+        let producer = thread::spawn(move || {
+            let mut p = p;
+            p.write_exact(&[1, 2]);
+            thread::sleep(std::time::Duration::from_millis(10));
+        });
+        let mut dst = [0; 4];
+        assert_eq!(c.read_blocking(&mut dst), 2);
+        assert_eq!(dst, [1, 2, 0, 0]);
+        producer.join().unwrap();
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_waiter() {
+        // One thread serves two consumers, parking while both are empty.
+        let (mut p1, mut c1) = create_ring_buffer::<u32>(4);
+        let (mut p2, mut c2) = create_ring_buffer::<u32>(4);
+        let (w1, w2) = (c1.waiter(), c2.waiter());
+        let writer = thread::spawn(move || {
+            for i in 0..1000 {
+                p1.write_blocking(&[i]);
+                p2.write_blocking(&[i * 2]);
+            }
+        });
+        let (mut sum1, mut sum2, mut count) = (0, 0, 0);
+        while count < 2000 {
+            w1.arm();
+            w2.arm();
+            let mut progress = false;
+            while let Some(value) = c1.read_element() {
+                sum1 += value;
+                count += 1;
+                progress = true;
+            }
+            while let Some(value) = c2.read_element() {
+                sum2 += value;
+                count += 1;
+                progress = true;
+            }
+            if !progress {
+                thread::park();
+            }
+        }
+        writer.join().unwrap();
+        assert_eq!((sum1, sum2), (499_500, 999_000));
+
+        // A live waiter keeps the ring buffer shared.
+        let (p, c) = create_ring_buffer::<u8>(4);
+        let waiter = p.waiter();
+        assert!(p.reunite(c).is_err());
+        drop(waiter);
+    }
+
+    #[cfg(feature = "std-blocking")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_waiter_with_blocking_read() {
+        use std::time::{Duration, Instant};
+
+        // Arming the waiter on another thread does not keep the thread blocked
+        // in `read_timeout` from being unparked.
+        let (mut p, mut c) = create_ring_buffer::<u8>(4);
+        let waiter = c.waiter();
+        let reader = thread::spawn(move || {
+            let start = Instant::now();
+            let mut dst = [0; 1];
+            (c.read_timeout(&mut dst, Duration::from_secs(10)), start.elapsed())
+        });
+        thread::sleep(Duration::from_millis(100));
+        waiter.arm();
+        assert!(p.write_element(1));
+        let (read, elapsed) = reader.join().unwrap();
+        assert_eq!(read, 1);
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_read_write() {
+        use futures::executor::block_on;
+
+        const COUNT: usize = 100_000;
+        let (p, c) = create_ring_buffer::<usize>(100);
+        let (mut p, mut c) = (p.into_async(), c.into_async());
+        let producer = thread::spawn(move || {
+            block_on(async {
+                let mut next = 0;
+                while next < COUNT {
+                    next += p
+                        .write_slices(
+                            |data, _| {
+                                let len = data.len().min(COUNT - next);
+                                for (i, value) in data[..len].iter_mut().enumerate() {
+                                    *value = next + i;
+                                }
+                                len
+                            },
+                            None,
+                        )
+                        .await;
+                }
+            });
+        });
+        block_on(async {
+            let mut expected = 0;
+            loop {
+                let read = c
+                    .read_slices(
+                        |data, _| {
+                            for value in data {
+                                assert_eq!(*value, expected);
+                                expected += 1;
+                            }
+                            data.len()
+                        },
+                        None,
+                    )
+                    .await;
+                if read == 0 {
+                    break;
+                }
+            }
+            assert_eq!(expected, COUNT);
+        });
+        producer.join().unwrap();
+
+        // The producer is woken when the consumer is dropped.
+        let (p, c) = create_ring_buffer::<u8>(2);
+        let mut p = p.into_async();
+        p.get_mut().write_exact(&[1, 2]);
+        let consumer = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drop(c);
+        });
+        assert_eq!(block_on(p.write_slices(|data, _| data.len(), None)), 0);
+        consumer.join().unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_write_all_read_exact() {
+        use futures::executor::block_on;
+
+        let src: Vec<u32> = (0..10_000).collect();
+        let (p, c) = create_ring_buffer::<u32>(77);
+        let (mut p, mut c) = (p.into_async(), c.into_async());
+        let producer = thread::spawn(move || {
+            let src: Vec<u32> = (0..10_000).collect();
+            for chunk in src.chunks(333) {
+                assert_eq!(block_on(p.write_all(chunk)), chunk.len());
+            }
+        });
+        let mut dst = vec![0; 10_000];
+        for chunk in dst.chunks_mut(1_000) {
+            assert_eq!(block_on(c.read_exact(chunk)), 1_000);
+        }
+        producer.join().unwrap();
+        assert_eq!(dst, src);
+
+        // The transfer stops short when the other handle is dropped.
+        let (p, c) = create_ring_buffer::<u32>(4);
+        let mut p = p.into_async();
+        let consumer = thread::spawn(move || {
+            let mut c = c;
+            let mut dst = [0; 6];
+            while c.read_into_slice(&mut dst) == 0 {
+                thread::yield_now();
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        });
+        let written = block_on(p.write_all(&[1; 100]));
+        assert!((4..=10).contains(&written));
+        consumer.join().unwrap();
+
+        let (p, c) = create_ring_buffer::<u32>(4);
+        let mut c = c.into_async();
+        let producer = thread::spawn(move || {
+            let mut p = p;
+            p.write_exact(&[1, 2, 3]);
+            thread::sleep(std::time::Duration::from_millis(10));
+        });
+        let mut dst = [0; 8];
+        assert_eq!(block_on(c.read_exact(&mut dst)), 3);
+        assert_eq!(dst[..3], [1, 2, 3]);
+        producer.join().unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_executors() {
+        let src: Vec<u32> = (0..10_000).collect();
+
+        // Both tasks run on a single thread, so each side makes progress only
+        // when woken by the other.
+        let (p, c) = create_ring_buffer::<u32>(16);
+        let (mut p, mut c) = (p.into_async(), c.into_async());
+        let executor = smol::LocalExecutor::new();
+        let dst = smol::block_on(executor.run(async {
+            let src = src.clone();
+            let producer = executor.spawn(async move { p.write_all(&src).await });
+            let consumer = executor.spawn(async move {
+                let mut dst = vec![0; 10_000];
+                assert_eq!(c.read_exact(&mut dst).await, 10_000);
+                dst
+            });
+            assert_eq!(producer.await, 10_000);
+            consumer.await
+        }));
+        assert_eq!(dst, src);
+
+        let (p, c) = create_ring_buffer::<u32>(16);
+        let (mut p, mut c) = (p.into_async(), c.into_async());
+        let dst = async_std::task::block_on(async {
+            let src = src.clone();
+            let producer = async_std::task::spawn(async move { p.write_all(&src).await });
+            let mut dst = vec![0; 10_000];
+            assert_eq!(c.read_exact(&mut dst).await, 10_000);
+            assert_eq!(producer.await, 10_000);
+            dst
+        });
+        assert_eq!(dst, src);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_closed() {
+        use futures::{executor::block_on, future};
+
+        // The producer stops writing to a full buffer once the consumer is gone.
+        let (p, c) = create_ring_buffer::<u32>(4);
+        let mut p = p.into_async();
+        let consumer = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drop(c);
+        });
+        let closed = p.closed();
+        let src = [1; 100];
+        let mut write = p.write_all(&src);
+        block_on(future::select(closed, &mut write));
+        assert_eq!(write.written(), 4);
+        consumer.join().unwrap();
+
+        // The consumer is notified even if elements are left to read.
+        let (mut p, c) = create_ring_buffer::<u32>(4);
+        p.write_exact(&[1, 2]);
+        let producer = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drop(p);
+        });
+        block_on(c.closed());
+        producer.join().unwrap();
+        assert_eq!(c.available(), 2);
+        block_on(c.closed());
+
+        // A pending future keeps the ring buffer shared.
+        let (p, c) = create_ring_buffer::<u8>(4);
+        let closed = c.closed();
+        assert!(p.reunite(c).is_err());
+        drop(closed);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_cancellation() {
+        use futures::{
+            executor::block_on,
+            future::{self, Either},
+        };
+        use std::pin::pin;
+
+        // Reads raced against a future that is always ready are cancelled
+        // whenever the buffer is empty, without losing any element.
+        let (mut p, c) = create_ring_buffer::<u32>(16);
+        let mut c = c.into_async();
+        let producer = thread::spawn(move || {
+            for value in 0..2_000 {
+                while !p.write_element(value) {
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut received = Vec::new();
+        while received.len() < 2_000 {
+            let read = pin!(c.read_slices(
+                |data, _| {
+                    received.extend_from_slice(data);
+                    data.len()
+                },
+                None,
+            ));
+            block_on(future::select(read, future::ready(())));
+        }
+        producer.join().unwrap();
+        assert_eq!(received, (0..2_000).collect::<Vec<_>>());
+
+        // A cancelled transfer of a whole slice resumes where it stopped.
+        let (p, c) = create_ring_buffer::<u32>(4);
+        let (mut p, mut c) = (p.into_async(), c.into_async());
+        let src = [1, 2, 3, 4, 5, 6];
+        let mut write = p.write_all(&src);
+        let result = block_on(future::select(&mut write, future::ready(())));
+        assert!(matches!(result, Either::Right(_)));
+        let written = write.written();
+        assert_eq!(written, 4);
+        let mut dst = [0; 6];
+        let mut read = c.read_exact(&mut dst);
+        let result = block_on(future::select(&mut read, future::ready(())));
+        assert!(matches!(result, Either::Right(_)));
+        assert_eq!(read.read(), 4);
+        assert_eq!(block_on(p.write_all(&src[written..])), 2);
+        assert_eq!(block_on(c.read_exact(&mut dst[4..])), 2);
+        assert_eq!(dst, src);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_static_waker() {
+        use std::{
+            future::Future,
+            pin::pin,
+            sync::atomic::{AtomicUsize, Ordering},
+            task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+        };
+
+        // A waker without allocation, like the ones of embedded executors.
+        static WAKES: AtomicUsize = AtomicUsize::new(0);
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(std::ptr::null(), &VTABLE),
+            |_| {
+                WAKES.fetch_add(1, Ordering::Relaxed);
+            },
+            |_| {
+                WAKES.fetch_add(1, Ordering::Relaxed);
+            },
+            |_| {},
+        );
+        let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+
+        let (mut p, c) = create_ring_buffer::<u8>(4);
+        let mut c = c.into_async();
+        let mut dst = [0; 2];
+        let mut read = pin!(c.read_exact(&mut dst));
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(WAKES.load(Ordering::Relaxed), 0);
+        // Written as from an interrupt handler, which wakes the task once.
+        p.write_element(1);
+        assert_eq!(WAKES.load(Ordering::Relaxed), 1);
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        p.write_element(2);
+        assert_eq!(WAKES.load(Ordering::Relaxed), 2);
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Ready(2));
+        // No task is registered anymore.
+        p.write_element(3);
+        assert_eq!(WAKES.load(Ordering::Relaxed), 2);
+        assert_eq!(dst, [1, 2]);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_consumer_stream() {
+        use futures::{executor::block_on, StreamExt};
+
+        fn spawn_producer(mut p: Producer<u32>) -> thread::JoinHandle<()> {
+            thread::spawn(move || {
+                for value in 0..10_000 {
+                    while !p.write_element(value) {
+                        thread::yield_now();
+                    }
+                }
+            })
+        }
+
+        let (p, c) = create_ring_buffer::<u32>(64);
+        let producer = spawn_producer(p);
+        let elements: Vec<u32> = block_on(c.into_stream().collect());
+        producer.join().unwrap();
+        assert_eq!(elements, (0..10_000).collect::<Vec<_>>());
+
+        let (p, c) = create_ring_buffer::<u32>(64);
+        let producer = spawn_producer(p);
+        let chunks: Vec<Vec<u32>> = block_on(c.into_chunk_stream(10).collect());
+        producer.join().unwrap();
+        assert!(chunks
+            .iter()
+            .all(|chunk| !chunk.is_empty() && chunk.len() <= 10));
+        assert_eq!(chunks.concat(), (0..10_000).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_frame_stream() {
+        use futures::{executor::block_on, StreamExt};
+
+        let (mut p, c) = create_ring_buffer::<u32>(10);
+        let producer = thread::spawn(move || {
+            for value in 0..10_001 {
+                while !p.write_element(value) {
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut frames = c.frames::<4>();
+        let mut expected = 0;
+        block_on(async {
+            while let Some(frame) = frames.next().await {
+                assert_eq!(frame, [expected, expected + 1, expected + 2, expected + 3]);
+                expected += 4;
+            }
+        });
+        producer.join().unwrap();
+        assert_eq!(expected, 10_000);
+        // The partial frame is left in the ring buffer.
+        let mut c = frames.into_inner();
+        assert_eq!(c.read_element(), Some(10_000));
+
+        let (_, c) = create_ring_buffer::<u32>(3);
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| c.frames::<4>())).is_err());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_producer_sink() {
+        use direct_ring_buffer::Closed;
+        use futures::{executor::block_on, stream, SinkExt, StreamExt};
+
+        let (p, c) = create_ring_buffer::<u32>(64);
+        let consumer = thread::spawn(move || block_on(c.into_stream().collect::<Vec<_>>()));
+        block_on(async {
+            let mut sink = p.into_sink();
+            let mut elements = stream::iter(0..5_000).map(Ok);
+            sink.send_all(&mut elements).await.unwrap();
+            // Batches larger than the capacity are written as space is freed.
+            for start in (5_000..10_000).step_by(500) {
+                sink.send((start..start + 500).collect::<Vec<_>>())
+                    .await
+                    .unwrap();
+            }
+        });
+        assert_eq!(consumer.join().unwrap(), (0..10_000).collect::<Vec<_>>());
+
+        let (p, c) = create_ring_buffer::<u32>(2);
+        let mut sink = p.into_sink();
+        drop(c);
+        assert_eq!(block_on(sink.send(1)), Err(Closed));
+        assert_eq!(block_on(sink.send(vec![1, 2, 3])), Err(Closed));
+    }
+
+    #[cfg(feature = "futures-io")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_async_buf_read() {
+        use futures::{executor::block_on, AsyncBufReadExt, AsyncReadExt};
+
+        // Lines straddle the wrap-around point of the small buffer.
+        let (mut p, c) = create_ring_buffer::<u8>(7);
+        let producer = thread::spawn(move || {
+            for i in 0..1000 {
+                let line = format!("{i}\n");
+                let mut bytes = line.as_bytes();
+                while !bytes.is_empty() {
+                    let written = p.write_from_slice(bytes);
+                    bytes = &bytes[written..];
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut c = c.into_async();
+        block_on(async {
+            let mut line = Vec::new();
+            for i in 0..1000 {
+                line.clear();
+                c.read_until(b'\n', &mut line).await.unwrap();
+                assert_eq!(line, format!("{i}\n").as_bytes());
+            }
+            let mut rest = Vec::new();
+            assert_eq!(c.read_to_end(&mut rest).await.unwrap(), 0);
+        });
+        producer.join().unwrap();
+    }
+
+    #[cfg(all(feature = "mio", unix))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_mio_notifier() {
+        use mio::{Events, Interest, Poll, Token};
+        use std::time::Duration;
+
+        const CONSUMER: Token = Token(0);
+        const PRODUCER: Token = Token(1);
+        let (mut p, mut c) = create_ring_buffer::<u32>(16);
+        let mut poll = Poll::new().unwrap();
+        let mut data = c.notifier().unwrap();
+        let mut space = p.notifier().unwrap();
+        poll.registry()
+            .register(&mut data, CONSUMER, Interest::READABLE)
+            .unwrap();
+        let mut producer_poll = Poll::new().unwrap();
+        producer_poll
+            .registry()
+            .register(&mut space, PRODUCER, Interest::READABLE)
+            .unwrap();
+
+        // The producer waits for space through its own event loop.
+        let producer = thread::spawn(move || {
+            let mut events = Events::with_capacity(4);
+            let mut next = 0;
+            while next < 10_000 {
+                space.arm();
+                let written = p.write_slices(
+                    |data, _| {
+                        let len = data.len().min(10_000 - next as usize);
+                        for value in &mut data[..len] {
+                            *value = next;
+                            next += 1;
+                        }
+                        len
+                    },
+                    None,
+                );
+                if written == 0 {
+                    producer_poll
+                        .poll(&mut events, Some(Duration::from_secs(10)))
+                        .unwrap();
+                    assert!(!events.is_empty());
+                }
+            }
+        });
+        let mut events = Events::with_capacity(4);
+        let mut expected = 0;
+        loop {
+            data.arm();
+            let read = c.read_slices(
+                |data, _| {
+                    for value in data {
+                        assert_eq!(*value, expected);
+                        expected += 1;
+                    }
+                    data.len()
+                },
+                None,
+            );
+            if read == 0 {
+                if expected == 10_000 {
+                    break;
+                }
+                poll.poll(&mut events, Some(Duration::from_secs(10)))
+                    .unwrap();
+                assert!(!events.is_empty());
+            }
+        }
+        producer.join().unwrap();
+
+        // The drop of the producer is signaled as well.
+        let (p, c) = create_ring_buffer::<u32>(16);
+        let mut data = c.notifier().unwrap();
+        poll.registry()
+            .register(&mut data, CONSUMER, Interest::READABLE)
+            .unwrap();
+        data.arm();
+        thread::spawn(move || drop(p)).join().unwrap();
+        poll.poll(&mut events, Some(Duration::from_secs(10)))
+            .unwrap();
+        assert!(events.iter().any(|event| event.token() == CONSUMER));
+    }
+
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_uring_iovecs() {
+        let (mut p, mut c) = create_ring_buffer::<u8>(10);
+        let buffer = p.uring_buffer();
+        assert_eq!(buffer.iov_base, p.as_mut_ptr().cast());
+        assert_eq!(buffer.iov_len, 10);
+
+        // `readv` stands in for `IORING_OP_READV`, which fills the iovecs the
+        // same way.
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let data: Vec<u8> = (0..100).collect();
+        let mut received = Vec::new();
+        for chunk in data.chunks(7) {
+            assert_eq!(
+                unsafe { libc::write(fds[1], chunk.as_ptr().cast(), chunk.len()) },
+                chunk.len() as isize
+            );
+            let iovecs = p.uring_iovecs(chunk.len());
+            assert!(!iovecs.is_empty() && iovecs.len() <= 2);
+            assert_eq!(iovecs.total_len(), chunk.len());
+            let result = unsafe { libc::readv(fds[0], iovecs.as_ptr(), iovecs.len() as i32) };
+            assert_eq!(
+                unsafe { p.uring_complete(result as i32) }.unwrap(),
+                chunk.len()
+            );
+            c.read_slices(
+                |data, _| {
+                    received.extend_from_slice(data);
+                    data.len()
+                },
+                None,
+            );
+        }
+        assert_eq!(received, data);
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+
+        p.write_exact(&[0; 10]);
+        assert!(p.uring_iovecs(usize::MAX).is_empty());
+        let error = unsafe { p.uring_complete(-libc::EAGAIN) }.unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WouldBlock);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_concurrent_io_would_block() {
+        use std::io::{ErrorKind, Read, Write};
+
+        let (mut p, mut c) = create_ring_buffer::<u8>(7);
+        let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+        let expected = data.clone();
+        // Retries on `WouldBlock` like code written for nonblocking sockets.
+        let writer = thread::spawn(move || {
+            let mut remaining = &data[..];
+            while !remaining.is_empty() {
+                match Write::write(&mut p, remaining) {
+                    Ok(n) => remaining = &remaining[n..],
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => thread::yield_now(),
+                    Err(e) => panic!("{e}"),
+                }
+            }
+        });
+        let mut received = Vec::new();
+        let mut buf = [0; 5];
+        loop {
+            match Read::read(&mut c, &mut buf) {
+                Ok(0) => break,
+                Ok(n) => received.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => thread::yield_now(),
+                Err(e) => panic!("{e}"),
+            }
+        }
+        writer.join().unwrap();
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
     fn test_concurrent_slices_read_write() {
         let (mut p, mut c) = create_ring_buffer::<usize>(44100);
         const TEST_LIMIT: usize = 50_000_000;
@@ -540,4 +3460,97 @@ mod tests {
         let _ = p.join();
         let _ = c.join();
     }
+
+    // Small enough to run under Miri, which checks that the producer and the
+    // consumer never create aliasing references to the storage.
+    #[test]
+    fn test_concurrent_slices_small() {
+        const TEST_LIMIT: usize = 300;
+        let (mut p, mut c) = create_ring_buffer::<usize>(7);
+
+        let p = thread::spawn(move || {
+            let mut write_value = 0;
+            while write_value != TEST_LIMIT {
+                let unit = (write_value % 4 + 1).min(TEST_LIMIT - write_value);
+                p.write_slices(
+                    |buf, _| {
+                        for value in buf.iter_mut() {
+                            *value = write_value;
+                            write_value += 1;
+                        }
+                        buf.len()
+                    },
+                    Some(unit),
+                );
+                let (first, second) = p.vacant_slices_mut();
+                let n = (first.len() + second.len()).min(TEST_LIMIT - write_value);
+                for (i, value) in first
+                    .iter_mut()
+                    .chain(second.iter_mut())
+                    .take(n)
+                    .enumerate()
+                {
+                    *value = write_value + i;
+                }
+                write_value += p.commit(n);
+                thread::yield_now();
+            }
+        });
+
+        let c = thread::spawn(move || {
+            let mut read_value = 0;
+            while read_value != TEST_LIMIT {
+                let unit = read_value % 3 + 1;
+                c.read_slices(
+                    |buf, _| {
+                        for value in buf {
+                            assert_eq!(*value, read_value);
+                            read_value += 1;
+                        }
+                        buf.len()
+                    },
+                    Some(unit),
+                );
+                let (first, second) = c.as_slices();
+                for (i, value) in first.iter().chain(second).enumerate() {
+                    assert_eq!(*value, read_value + i);
+                }
+                read_value += c.advance(first.len() + second.len());
+                thread::yield_now();
+            }
+        });
+        p.join().unwrap();
+        c.join().unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_non_copy_small() {
+        const TEST_LIMIT: usize = 200;
+        let (mut p, mut c) = create_ring_buffer::<String>(5);
+
+        let p = thread::spawn(move || {
+            for value in 0..TEST_LIMIT {
+                let mut element = value.to_string();
+                while let Err(e) = p.try_write_element(element) {
+                    element = e;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let c = thread::spawn(move || {
+            let mut read_value = 0;
+            while read_value != TEST_LIMIT {
+                match c.read_element() {
+                    Some(element) => {
+                        assert_eq!(element, read_value.to_string());
+                        read_value += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+        });
+        p.join().unwrap();
+        c.join().unwrap();
+    }
 }